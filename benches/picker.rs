@@ -0,0 +1,66 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use egui_color_picker_oklab::{
+    color_edit_button_oklch, convert, extract_palette, open_color_picker, ColorEditButton, Gradient, OklchA,
+};
+
+fn conversions(c: &mut Criterion) {
+    let colors: Vec<OklchA> = (0..1024)
+        .map(|i| OklchA::new((i % 32) as f32 / 32.0, 0.2, i as f32 / 100.0, 1.0))
+        .collect();
+
+    c.bench_function("oklch_to_srgb8 x1024", |b| {
+        b.iter(|| {
+            for &color in &colors {
+                black_box(convert::oklch_to_srgb8(color));
+            }
+        })
+    });
+    c.bench_function("max_chroma_for", |b| b.iter(|| convert::max_chroma_for(black_box(0.7), black_box(1.0))));
+
+    let gradient = Gradient::from_colors(&colors[..4]);
+    c.bench_function("gradient to_image 256x16", |b| {
+        b.iter(|| gradient.to_image(256, 16, convert::Dither::Ordered))
+    });
+
+    let pixels: Vec<egui::Color32> = (0..64 * 64)
+        .map(|i| egui::Color32::from_rgb((i % 64 * 4) as u8, (i / 64 * 4) as u8, 128))
+        .collect();
+    c.bench_function("extract_palette 64x64 k=8", |b| b.iter(|| extract_palette(&pixels, 8)));
+}
+
+/// One whole frame: layout, mesh generation and tessellation.
+fn frame(c: &mut Criterion, name: &str, mut add_contents: impl FnMut(&mut egui::Ui)) {
+    let mut ctx = egui::CtxRef::default();
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            ctx.begin_frame(egui::RawInput::default());
+            egui::CentralPanel::default().show(&ctx, |ui| add_contents(ui));
+            let (_output, shapes) = ctx.end_frame();
+            black_box(ctx.tessellate(shapes));
+        })
+    });
+}
+
+fn widgets(c: &mut Criterion) {
+    let mut color = OklchA::new(0.7, 0.15, 2.0, 1.0);
+    frame(c, "closed button", |ui| {
+        color_edit_button_oklch(ui, &mut color);
+    });
+
+    let id = egui::Id::new("bench_picker");
+    frame(c, "open picker", |ui| {
+        open_color_picker(ui.ctx(), id);
+        ColorEditButton::new(&mut color).id(id).show(ui);
+    });
+
+    let mut colors = vec![OklchA::new(0.7, 0.15, 2.0, 1.0); 200];
+    frame(c, "200 closed buttons", |ui| {
+        for color in &mut colors {
+            color_edit_button_oklch(ui, color);
+        }
+    });
+}
+
+criterion_group!(benches, conversions, widgets);
+criterion_main!(benches);
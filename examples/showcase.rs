@@ -0,0 +1,262 @@
+//! Every entry point and most options of the crate in one window, as a manual test bed.
+//!
+//! `cargo run --example showcase`
+
+use eframe::{egui, epi};
+use egui::color_picker::Alpha;
+use egui::{Color32, Rgba};
+
+use egui_color_picker_oklab::{
+    color_edit_button, color_edit_button_multi, color_edit_button_oklab, color_edit_button_rgba, gradient_edit,
+    stroke_edit, stroke_fill_edit,
+    AlphaDisplay, Backdrop, ButtonTooltip, ColorEditButton, Gradient, MarkerShape, MarkerStyle, OklabA, OklchA,
+    Palette, PickerMode, SharedPicker, Snap, WhitePoint,
+};
+
+struct Showcase {
+    oklch: OklchA,
+    oklab: OklabA,
+    color32: Color32,
+    rgba: Rgba,
+    group: [Color32; 3],
+    stroke: Color32,
+    fill: Color32,
+    line: egui::Stroke,
+    mode: PickerMode,
+    alpha_display: AlphaDisplay,
+    solid_backdrop: bool,
+    rich_tooltip: bool,
+    read_only: bool,
+    overlays: bool,
+    snap: bool,
+    cmyk: bool,
+    theme_preview: bool,
+    bar_markers: bool,
+    palette: Palette,
+    indexed: OklchA,
+    index: Option<usize>,
+    swatches: Vec<OklchA>,
+    gradient: Gradient,
+}
+
+impl Default for Showcase {
+    fn default() -> Self {
+        let mut palette = Palette::new();
+        palette.push("Ink", OklchA::new(0.25, 0.02, 4.5, 1.0));
+        palette.push("Coral", OklchA::new(0.72, 0.15, 0.6, 1.0));
+        palette.push("Sand", OklchA::new(0.88, 0.05, 1.3, 1.0));
+        palette.push("Teal", OklchA::new(0.6, 0.1, -2.9, 1.0));
+        palette.push("Sky", OklchA::new(0.8, 0.09, -2.2, 1.0));
+
+        Self {
+            oklch: OklchA::new(0.7, 0.15, 2.0, 0.8),
+            oklab: OklabA::new(0.6, 0.1, -0.05, 1.0),
+            color32: Color32::from_rgb(200, 80, 40),
+            rgba: Rgba::from_rgba_premultiplied(0.1, 0.2, 0.4, 0.5),
+            group: [
+                Color32::from_rgb(220, 90, 70),
+                Color32::from_rgb(240, 170, 60),
+                Color32::from_rgb(90, 160, 120),
+            ],
+            stroke: Color32::from_rgb(30, 30, 60),
+            fill: Color32::from_rgb(250, 210, 90),
+            line: egui::Stroke::new(2.0, Color32::from_rgb(90, 140, 250)),
+            mode: PickerMode::default(),
+            alpha_display: AlphaDisplay::default(),
+            solid_backdrop: false,
+            rich_tooltip: true,
+            read_only: false,
+            overlays: false,
+            snap: false,
+            cmyk: false,
+            theme_preview: false,
+            bar_markers: false,
+            indexed: palette.entries[1].color,
+            index: Some(1),
+            swatches: palette.entries.iter().map(|entry| entry.color).collect(),
+            palette,
+            gradient: Gradient::from_colors(&[
+                OklchA::new(0.45, 0.15, -1.8, 1.0),
+                OklchA::new(0.9, 0.12, 1.6, 1.0),
+            ]),
+        }
+    }
+}
+
+fn mode_name(mode: PickerMode) -> &'static str {
+    match mode {
+        PickerMode::Oklch => "Oklch",
+        PickerMode::Oklab => "Oklab",
+        PickerMode::CieLab(_) => "CIE Lab",
+        PickerMode::CieLch(_) => "CIE LCh",
+        PickerMode::Hsluv => "HSLuv",
+        PickerMode::Temperature => "Temperature",
+    }
+}
+
+impl Showcase {
+    fn options_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Mode")
+            .selected_text(mode_name(self.mode))
+            .show_ui(ui, |ui| {
+                for &mode in &[
+                    PickerMode::Oklch,
+                    PickerMode::Oklab,
+                    PickerMode::CieLab(WhitePoint::D50),
+                    PickerMode::CieLch(WhitePoint::D65),
+                    PickerMode::Hsluv,
+                    PickerMode::Temperature,
+                ] {
+                    ui.selectable_value(&mut self.mode, mode, mode_name(mode));
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.label("Alpha:");
+            ui.radio_value(&mut self.alpha_display, AlphaDisplay::SplitHalves, "Split");
+            ui.radio_value(&mut self.alpha_display, AlphaDisplay::BlendedOverCheckers, "Blended");
+            ui.radio_value(&mut self.alpha_display, AlphaDisplay::OpaqueOnly, "Opaque");
+        });
+        ui.checkbox(&mut self.solid_backdrop, "Solid backdrop instead of checkers");
+        ui.checkbox(&mut self.rich_tooltip, "Rich tooltip");
+        ui.checkbox(&mut self.read_only, "Read-only");
+        ui.checkbox(&mut self.overlays, "Gamut boundary, perceptual grid and clamping");
+        ui.checkbox(&mut self.snap, "Snap to 5% lightness and 15° hue steps");
+        ui.checkbox(&mut self.cmyk, "CMYK");
+        ui.checkbox(&mut self.theme_preview, "Theme preview");
+        ui.checkbox(&mut self.bar_markers, "Bar markers");
+    }
+
+    fn configured<'a>(&self, button: ColorEditButton<'a>) -> ColorEditButton<'a> {
+        let backdrop = if self.solid_backdrop {
+            Backdrop::Solid(Color32::from_rgb(250, 246, 238))
+        } else {
+            Backdrop::default()
+        };
+        let snap = if self.snap {
+            Snap {
+                lightness: Some(0.05),
+                hue: Some(15f32.to_radians()),
+                show_grid: true,
+                ..Default::default()
+            }
+        } else {
+            Snap::default()
+        };
+        let marker = if self.bar_markers {
+            MarkerStyle {
+                shape_1d: MarkerShape::Bar,
+                shape_2d: MarkerShape::Bar,
+                ..Default::default()
+            }
+        } else {
+            MarkerStyle::default()
+        };
+        button
+            .mode(self.mode)
+            .alpha_display(self.alpha_display)
+            .backdrop(backdrop)
+            .tooltip(if self.rich_tooltip { ButtonTooltip::Rich } else { ButtonTooltip::Text })
+            .read_only(self.read_only)
+            .gamut_boundary(self.overlays)
+            .perceptual_grid(self.overlays)
+            .clamp_to_gamut(self.overlays)
+            .snap(snap)
+            .cmyk(self.cmyk)
+            .theme_preview(self.theme_preview)
+            .marker_style(marker)
+    }
+}
+
+impl epi::App for Showcase {
+    fn name(&self) -> &str {
+        "egui-color-picker-oklab showcase"
+    }
+
+    fn update(&mut self, ctx: &egui::CtxRef, _frame: &mut epi::Frame<'_>) {
+        egui::SidePanel::left("options").show(ctx, |ui| {
+            ui.heading("Options");
+            ui.label("Apply to the buttons marked ⚙.");
+            self.options_ui(ui);
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::Grid::new("entry_points").num_columns(2).show(ui, |ui| {
+                let mut oklch = self.oklch;
+                self.configured(ColorEditButton::new(&mut oklch)).show(ui);
+                self.oklch = oklch;
+                ui.label("⚙ ColorEditButton");
+                ui.end_row();
+
+                let mut palette = std::mem::take(&mut self.palette);
+                let mut oklch = self.oklch;
+                self.configured(ColorEditButton::new(&mut oklch))
+                    .id_salt("with_palette")
+                    .palette(&mut palette)
+                    .show(ui);
+                self.oklch = oklch;
+                self.palette = palette;
+                ui.label("⚙ …with a palette panel");
+                ui.end_row();
+
+                let mut indexed = self.indexed;
+                let mut index = self.index;
+                self.configured(ColorEditButton::new(&mut indexed))
+                    .indexed(&self.palette, &mut index)
+                    .show(ui);
+                self.indexed = indexed;
+                self.index = index;
+                ui.label(format!("⚙ Indexed to the palette: {:?}", self.index));
+                ui.end_row();
+
+                color_edit_button(ui, &mut self.color32);
+                ui.label("color_edit_button (Color32)");
+                ui.end_row();
+
+                color_edit_button_rgba(ui, &mut self.rgba, Alpha::OnlyBlend);
+                ui.label("color_edit_button_rgba (float Rgba)");
+                ui.end_row();
+
+                color_edit_button_oklab(ui, &mut self.oklab);
+                ui.label("color_edit_button_oklab");
+                ui.end_row();
+
+                let [a, b, c] = &mut self.group;
+                color_edit_button_multi(ui, &mut [a, b, c]);
+                ui.horizontal(|ui| {
+                    for color in &self.group {
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                        ui.painter().rect_filled(rect, 2.0, *color);
+                    }
+                    ui.label("color_edit_button_multi");
+                });
+                ui.end_row();
+
+                stroke_fill_edit(ui, &mut self.stroke, &mut self.fill);
+                ui.label("stroke_fill_edit");
+                ui.end_row();
+
+                stroke_edit(ui, &mut self.line);
+                ui.label("stroke_edit");
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.label("One shared picker window for many swatches:");
+            let shared = SharedPicker::new("showcase_swatches");
+            ui.horizontal(|ui| {
+                for (i, color) in self.swatches.iter_mut().enumerate() {
+                    shared.show(ui, i, ColorEditButton::new(color));
+                }
+            });
+
+            ui.separator();
+            ui.label("Gradient editor:");
+            gradient_edit(ui, &mut self.gradient);
+            ui.monospace(self.gradient.to_css_linear_gradient(90.0, 4));
+        });
+    }
+}
+
+fn main() {
+    eframe::run_native(Box::new(Showcase::default()), eframe::NativeOptions::default());
+}
@@ -0,0 +1,59 @@
+//! Smooth transitions between colors, for state indication.
+
+use egui::{CtxRef, Id};
+
+use crate::{convert, OklchA};
+
+/// A transition from `from` to `to` that started at time `start` (in seconds of
+/// [`egui::InputState::time`]).
+#[derive(Clone, Copy, Debug)]
+struct ColorAnimation {
+    from: OklchA,
+    to: OklchA,
+    start: f64,
+}
+
+impl ColorAnimation {
+    fn progress(&self, now: f64, animation_time: f32) -> f32 {
+        if animation_time <= 0.0 {
+            return 1.0;
+        }
+        ((now - self.start) as f32 / animation_time).clamp(0.0, 1.0)
+    }
+
+    fn at(&self, now: f64, animation_time: f32) -> OklchA {
+        convert::oklab_lerp(self.from, self.to, self.progress(now, animation_time))
+    }
+}
+
+/// The color the animation with the given `id` is at, moving in a straight line through Oklab
+/// to `target` over `animation_time` seconds whenever `target` changes. The first call starts
+/// out at `target`. Keeps requesting repaints until the animation is done.
+///
+/// ```ignore
+/// let target = if response.hovered() { hovered } else { idle };
+/// let color = animate_color(ui.ctx(), response.id.with("fill"), target, 0.15);
+/// ```
+pub fn animate_color(ctx: &CtxRef, id: Id, target: OklchA, animation_time: f32) -> OklchA {
+    let now = ctx.input().time;
+    let animation = match temp_get!(ctx, id, ColorAnimation) {
+        // Retargeting mid-way starts from wherever the color is now, so it never jumps.
+        Some(animation) if animation.to != target => ColorAnimation {
+            from: animation.at(now, animation_time),
+            to: target,
+            start: now,
+        },
+        Some(animation) => animation,
+        None => ColorAnimation {
+            from: target,
+            to: target,
+            start: now,
+        },
+    };
+    temp_insert!(ctx, id, animation);
+
+    if animation.progress(now, animation_time) < 1.0 {
+        ctx.request_repaint();
+    }
+    animation.at(now, animation_time)
+}
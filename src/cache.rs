@@ -1,11 +1,23 @@
-//! taken from egui internals
+//! The cache of the full Oklch colors behind 8-bit colors, which keeps the hue of grays while
+//! they are edited with [`color_edit_button`](crate::color_edit_button).
+//!
+//! [`Cache`] is taken from egui internals.
+
 use std::hash::{Hash, Hasher};
 
+use egui::{CtxRef, Id};
+
+use crate::widgets::EditSession;
+use crate::{compat, OklchA, Srgba};
+
 const SIZE: usize = 1024; // must be small for web/WASM build (for unknown reason)
 
 /// Very stupid/simple key-value cache. TODO: improve
+///
+/// Entries remember the frame they were last set in, so that [`Cache::gc`] can evict the ones
+/// nothing has used for a while. The second field is the frame of the last [`Cache::gc`].
 #[derive(Clone)]
-pub struct Cache<K, V>([Option<(K, V)>; SIZE]);
+pub(crate) struct Cache<K, V>([Option<(K, V, u64)>; SIZE], Option<u64>);
 
 impl<K, V> Default for Cache<K, V>
 where
@@ -13,7 +25,7 @@ where
     V: Copy,
 {
     fn default() -> Self {
-        Self([None; SIZE])
+        Self([None; SIZE], None)
     }
 }
 
@@ -30,14 +42,29 @@ where
     pub fn get(&self, key: &K) -> Option<&V> {
         let bucket = (hash(key) % (SIZE as u64)) as usize;
         match &self.0[bucket] {
-            Some((k, v)) if k == key => Some(v),
+            Some((k, v, _)) if k == key => Some(v),
             _ => None,
         }
     }
 
-    pub fn set(&mut self, key: K, value: V) {
+    /// Stores `value` at `key`, as used in frame number `frame`.
+    pub fn set(&mut self, key: K, value: V, frame: u64) {
         let bucket = (hash(&key) % (SIZE as u64)) as usize;
-        self.0[bucket] = Some((key, value));
+        self.0[bucket] = Some((key, value, frame));
+    }
+
+    /// Evicts the entries that were last set more than `max_age` frames before `frame`. Only the
+    /// first call in each frame scans the cache, so every button can call it.
+    pub fn gc(&mut self, frame: u64, max_age: u64) {
+        if self.1 == Some(frame) {
+            return;
+        }
+        self.1 = Some(frame);
+        for entry in self.0.iter_mut() {
+            if matches!(entry, Some((_, _, last_used)) if frame.saturating_sub(*last_used) > max_age) {
+                *entry = None;
+            }
+        }
     }
 }
 
@@ -47,3 +74,54 @@ fn hash(value: impl Hash) -> u64 {
     value.hash(&mut hasher);
     hasher.finish()
 }
+
+/// Colors not edited for this many frames are forgotten by the cache of
+/// [`color_edit_button`](crate::color_edit_button), so reopening them may reset the hue of grays.
+pub(crate) const CACHE_MAX_AGE_FRAMES: u64 = 60 * 60 * 10;
+
+/// Drops the shared cache of the float colors behind 8-bit colors, e.g. in applications that
+/// manage memory tightly. The hue of gray colors edited with
+/// [`color_edit_button`](crate::color_edit_button) may reset. The per-widget state of open or
+/// recently shown pickers (edit sessions, history, panel settings) is kept; egui
+/// drops it along with the rest of its temp memory.
+pub fn clear_color_picker_caches(ctx: &CtxRef) {
+    *ctx.memory().data_temp.get_mut_or_default::<Cache<[u8; 4], OklchA>>() = Cache::default();
+}
+
+/// The float color a [`color_edit_button`](crate::color_edit_button) with
+/// [`Response::id`](egui::Response::id) `id` would edit for the sRGB bytes `srgb8`
+/// (premultiplied alpha, as `Color32` and `Srgba::to_u8`), if any is remembered: that of its open
+/// session, its persisted one (with the `persistence` feature), or the shared cache's.
+pub fn get_cached_oklch(ctx: &CtxRef, id: Id, srgb8: [u8; 4]) -> Option<OklchA> {
+    let session = temp_get!(ctx, id.with("session"), EditSession<[u8; 4]>).filter(|session| session.applied == srgb8);
+    if let Some(session) = session {
+        return Some(session.oklch);
+    }
+    let cached = ctx
+        .memory()
+        .data_temp
+        .get_or_default::<Cache<[u8; 4], OklchA>>()
+        .get(&srgb8)
+        .cloned();
+    #[cfg(feature = "persistence")]
+    let cached = persisted_get!(ctx, id.with("oklch"), ([u8; 4], [f32; 4]))
+        .filter(|(persisted, _)| *persisted == srgb8)
+        .map(|(_, [l, c, h, alpha])| OklchA::new(l, c, h, alpha))
+        .or(cached);
+    cached
+}
+
+/// Remembers `oklch` as the float color of the [`color_edit_button`](crate::color_edit_button)
+/// with [`Response::id`](egui::Response::id) `id`, e.g. to restore the hue of gray colors when
+/// loading a document. It applies as long as the button's color is `oklch` quantized to 8 bits.
+#[cfg_attr(not(feature = "persistence"), allow(unused_variables))]
+pub fn set_cached_oklch(ctx: &CtxRef, id: Id, oklch: OklchA) {
+    let srgb8 = oklch.convert::<Srgba>().to_u8();
+    let frame = compat::frame_nr(ctx);
+    ctx.memory()
+        .data_temp
+        .get_mut_or_default::<Cache<[u8; 4], OklchA>>()
+        .set(srgb8, oklch, frame);
+    #[cfg(feature = "persistence")]
+    persisted_insert!(ctx, id.with("oklch"), (srgb8, [oklch.col.l, oklch.col.c, oklch.col.h, oklch.alpha]));
+}
@@ -0,0 +1,133 @@
+//! CIE L\*a\*b\* and LCh(ab) conversions, relative to a selectable white point.
+//!
+//! colstodian doesn't provide these spaces, so we go through CIE XYZ ourselves,
+//! starting from colstodian's linear sRGB.
+
+use colstodian::*;
+
+use crate::{LinearSrgbA, OklchA, Srgba};
+
+/// Reference white used for CIE L\*a\*b\*. sRGB itself is defined relative to D65;
+/// D50 values are obtained with a Bradford chromatic adaptation, matching ICC print workflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WhitePoint {
+    D50,
+    D65,
+}
+
+impl WhitePoint {
+    fn xyz(self) -> [f32; 3] {
+        match self {
+            WhitePoint::D50 => [0.964_22, 1.0, 0.825_21],
+            WhitePoint::D65 => [0.950_47, 1.0, 1.088_83],
+        }
+    }
+}
+
+pub(crate) fn mul(m: [[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+pub(crate) const LINEAR_SRGB_TO_XYZ_D65: [[f32; 3]; 3] = [
+    [0.412_456_4, 0.357_576_1, 0.180_437_5],
+    [0.212_672_9, 0.715_152_2, 0.072_175_0],
+    [0.019_333_9, 0.119_192_0, 0.950_304_1],
+];
+
+pub(crate) const XYZ_D65_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.240_454_2, -1.537_138_5, -0.498_531_4],
+    [-0.969_266_0, 1.876_010_8, 0.041_556_0],
+    [0.055_643_4, -0.204_025_9, 1.057_225_2],
+];
+
+const BRADFORD_D65_TO_D50: [[f32; 3]; 3] = [
+    [1.047_811_2, 0.022_886_6, -0.050_127_0],
+    [0.029_542_4, 0.990_484_4, -0.017_049_1],
+    [-0.009_234_5, 0.015_043_6, 0.752_131_6],
+];
+
+const BRADFORD_D50_TO_D65: [[f32; 3]; 3] = [
+    [0.955_576_6, -0.023_039_3, 0.063_163_6],
+    [-0.028_289_5, 1.009_941_6, 0.021_007_7],
+    [0.012_298_2, -0.020_483_0, 1.329_909_8],
+];
+
+const EPSILON: f32 = 6.0 / 29.0;
+
+fn lab_f(t: f32) -> f32 {
+    if t > EPSILON * EPSILON * EPSILON {
+        t.cbrt()
+    } else {
+        t / (3.0 * EPSILON * EPSILON) + 4.0 / 29.0
+    }
+}
+
+fn lab_f_inv(t: f32) -> f32 {
+    if t > EPSILON {
+        t * t * t
+    } else {
+        3.0 * EPSILON * EPSILON * (t - 4.0 / 29.0)
+    }
+}
+
+/// Linear sRGB to CIE L\*a\*b\*. L\* is in `0..=100`.
+pub fn lab_from_linear_srgb(rgb: [f32; 3], white_point: WhitePoint) -> [f32; 3] {
+    let xyz = mul(LINEAR_SRGB_TO_XYZ_D65, rgb);
+    let xyz = match white_point {
+        WhitePoint::D50 => mul(BRADFORD_D65_TO_D50, xyz),
+        WhitePoint::D65 => xyz,
+    };
+    let white = white_point.xyz();
+    let fx = lab_f(xyz[0] / white[0]);
+    let fy = lab_f(xyz[1] / white[1]);
+    let fz = lab_f(xyz[2] / white[2]);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIE L\*a\*b\* to linear sRGB. The result may be out of gamut.
+pub fn linear_srgb_from_lab(lab: [f32; 3], white_point: WhitePoint) -> [f32; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let white = white_point.xyz();
+    let xyz = [
+        white[0] * lab_f_inv(fx),
+        white[1] * lab_f_inv(fy),
+        white[2] * lab_f_inv(fz),
+    ];
+    let xyz = match white_point {
+        WhitePoint::D50 => mul(BRADFORD_D50_TO_D65, xyz),
+        WhitePoint::D65 => xyz,
+    };
+    mul(XYZ_D65_TO_LINEAR_SRGB, xyz)
+}
+
+/// CIE L\*a\*b\* to LCh(ab), with hue in degrees `0..360`.
+pub fn lch_from_lab([l, a, b]: [f32; 3]) -> [f32; 3] {
+    [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+/// CIE LCh(ab), with hue in degrees, to L\*a\*b\*.
+pub fn lab_from_lch([l, c, h]: [f32; 3]) -> [f32; 3] {
+    let (sin, cos) = h.to_radians().sin_cos();
+    [l, c * cos, c * sin]
+}
+
+pub(crate) fn oklch_to_lab(color: &OklchA, white_point: WhitePoint) -> [f32; 3] {
+    let lin: LinearSrgbA = color.convert();
+    lab_from_linear_srgb([lin.col.r, lin.col.g, lin.col.b], white_point)
+}
+
+pub(crate) fn lab_to_oklch(lab: [f32; 3], alpha: f32, white_point: WhitePoint) -> OklchA {
+    let [r, g, b] = linear_srgb_from_lab(lab, white_point);
+    LinearSrgbA::new(r, g, b, alpha).convert()
+}
+
+pub(crate) fn lab_to_srgba(lab: [f32; 3], alpha: f32, white_point: WhitePoint) -> Srgba {
+    let [r, g, b] = linear_srgb_from_lab(lab, white_point);
+    LinearSrgbA::new(r, g, b, alpha).convert()
+}
@@ -0,0 +1,42 @@
+//! Naive (ICC-less) CMYK, computed directly from encoded sRGB.
+//!
+//! This is only a ballpark view: real print conversions depend on the press profile.
+
+use colstodian::*;
+
+use crate::{EncodedSrgbA, OklchA, Srgba};
+
+/// Encoded sRGB (`0..=1`) to `[cyan, magenta, yellow, key]` (`0..=1`).
+pub fn cmyk_from_srgb([r, g, b]: [f32; 3]) -> [f32; 4] {
+    let k = 1.0 - r.max(g).max(b);
+    if k >= 1.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    [
+        (1.0 - r - k) / (1.0 - k),
+        (1.0 - g - k) / (1.0 - k),
+        (1.0 - b - k) / (1.0 - k),
+        k,
+    ]
+}
+
+/// `[cyan, magenta, yellow, key]` (`0..=1`) to encoded sRGB (`0..=1`).
+pub fn srgb_from_cmyk([c, m, y, k]: [f32; 4]) -> [f32; 3] {
+    [(1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k)]
+}
+
+pub(crate) fn oklch_to_cmyk(color: &OklchA) -> [f32; 4] {
+    let srgb: EncodedSrgbA = color.convert();
+    let srgb = srgb.saturate();
+    cmyk_from_srgb([srgb.col.r, srgb.col.g, srgb.col.b])
+}
+
+pub(crate) fn cmyk_to_oklch(cmyk: [f32; 4], alpha: f32) -> OklchA {
+    let [r, g, b] = srgb_from_cmyk(cmyk);
+    EncodedSrgbA::new(r, g, b, alpha).convert()
+}
+
+pub(crate) fn cmyk_to_srgba(cmyk: [f32; 4]) -> Srgba {
+    let [r, g, b] = srgb_from_cmyk(cmyk);
+    EncodedSrgbA::new(r, g, b, 1.0).convert()
+}
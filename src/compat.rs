@@ -0,0 +1,81 @@
+//! The memory, popup and focus calls of this crate, whose API has changed across egui releases,
+//! in one place. Porting to another egui version starts here, but isn't limited to this file:
+//! the typed app-wide state in `ctx.memory().data_temp`, `ui.input()` and `ui.output()`,
+//! `Label::text_color`, `Ui::set_enabled` and the corner radius arguments of the painter are
+//! still used directly throughout the crate.
+//!
+//! This crate is built against egui 0.14 (pinned in `Cargo.toml`), where memory is reached
+//! through a lock guard (`ctx.memory()`). Newer releases use closures (`ctx.memory_mut(|m| ..)`,
+//! `ctx.data_mut(|d| d.get_temp::<T>(id))`) and typed removal; the typed forms are kept in the
+//! macro signatures below so call sites don't change.
+
+use egui::{CtxRef, Id};
+
+/// `temp_get!(ctx, id, T) -> Option<T>`: a clone of the temporary value of type `T` at `id`.
+macro_rules! temp_get {
+    ($ctx:expr, $id:expr, $T:ty) => {
+        $ctx.memory().id_data_temp.get::<$T>(&$id).cloned()
+    };
+}
+
+/// `temp_insert!(ctx, id, value)`: stores a temporary value at `id`, replacing any previous one.
+macro_rules! temp_insert {
+    ($ctx:expr, $id:expr, $value:expr) => {{
+        $ctx.memory().id_data_temp.insert($id, $value);
+    }};
+}
+
+/// `temp_remove!(ctx, id, T)`: removes the temporary value at `id`, if any. egui 0.14 removes
+/// it whatever its type; `T` is the type expected there, as newer releases remove by type.
+macro_rules! temp_remove {
+    ($ctx:expr, $id:expr, $T:ty) => {{
+        $ctx.memory().id_data_temp.remove(&$id);
+    }};
+}
+
+/// `persisted_get!(ctx, id, T) -> Option<T>`: a clone of the persisted value of type `T` at
+/// `id`, which egui saves with the app's state (with its `persistence` feature).
+#[cfg(feature = "persistence")]
+macro_rules! persisted_get {
+    ($ctx:expr, $id:expr, $T:ty) => {
+        $ctx.memory().id_data.get::<$T>(&$id).cloned()
+    };
+}
+
+/// `persisted_insert!(ctx, id, value)`: stores a persisted value at `id`.
+#[cfg(feature = "persistence")]
+macro_rules! persisted_insert {
+    ($ctx:expr, $id:expr, $value:expr) => {{
+        $ctx.memory().id_data.insert($id, $value);
+    }};
+}
+
+pub(crate) fn is_popup_open(ctx: &CtxRef, id: Id) -> bool {
+    ctx.memory().is_popup_open(id)
+}
+
+pub(crate) fn open_popup(ctx: &CtxRef, id: Id) {
+    ctx.memory().open_popup(id);
+}
+
+pub(crate) fn toggle_popup(ctx: &CtxRef, id: Id) {
+    ctx.memory().toggle_popup(id);
+}
+
+pub(crate) fn close_popup(ctx: &CtxRef) {
+    ctx.memory().close_popup();
+}
+
+/// The widget with keyboard focus, if any.
+pub(crate) fn focused(ctx: &CtxRef) -> Option<Id> {
+    ctx.memory().focus()
+}
+
+pub(crate) fn request_focus(ctx: &CtxRef, id: Id) {
+    ctx.memory().request_focus(id);
+}
+
+/// The number of frames since the context was created.
+pub(crate) fn frame_nr(ctx: &CtxRef) -> u64 {
+    ctx.frame_nr()
+}
@@ -0,0 +1,104 @@
+//! Alpha compositing of straight-alpha colors, and its inverse: the translucent color that
+//! composites over a background to a target color.
+
+use crate::{EncodedSrgbA, LinearSrgbA, OklchA};
+
+/// Where colors are blended when compositing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompositeSpace {
+    /// Blending the encoded sRGB values, as browsers and most image editors do.
+    EncodedSrgb,
+    /// Blending in linear light, as GPUs do with sRGB framebuffers.
+    LinearSrgb,
+}
+
+impl Default for CompositeSpace {
+    fn default() -> Self {
+        CompositeSpace::EncodedSrgb
+    }
+}
+
+impl CompositeSpace {
+    fn to_rgba(self, color: OklchA) -> [f32; 4] {
+        match self {
+            CompositeSpace::EncodedSrgb => {
+                let c = color.convert::<EncodedSrgbA>();
+                [c.col.r, c.col.g, c.col.b, c.alpha]
+            }
+            CompositeSpace::LinearSrgb => {
+                let c = color.convert::<LinearSrgbA>();
+                [c.col.r, c.col.g, c.col.b, c.alpha]
+            }
+        }
+    }
+
+    fn from_rgba(self, [r, g, b, alpha]: [f32; 4]) -> OklchA {
+        match self {
+            CompositeSpace::EncodedSrgb => EncodedSrgbA::new(r, g, b, alpha).convert(),
+            CompositeSpace::LinearSrgb => LinearSrgbA::new(r, g, b, alpha).convert(),
+        }
+    }
+}
+
+/// `color` composited over `background` ("source over"), both with straight alpha.
+pub fn composite_over(color: OklchA, background: OklchA, space: CompositeSpace) -> OklchA {
+    let [cr, cg, cb, ca] = space.to_rgba(color);
+    let [br, bg, bb, ba] = space.to_rgba(background);
+    let alpha = ca + ba * (1.0 - ca);
+    if alpha <= 0.0 {
+        return OklchA::new(0.0, 0.0, 0.0, 0.0);
+    }
+    let over = |c: f32, b: f32| (c * ca + b * ba * (1.0 - ca)) / alpha;
+    space.from_rgba([over(cr, br), over(cg, bg), over(cb, bb), alpha])
+}
+
+/// The straight-alpha color that gives `target` when composited over the opaque `background`
+/// with [`composite_over`], e.g. for an overlay or hover tint. The alpha of both is ignored.
+///
+/// With `alpha`, the color with that alpha, or `None` if there is none in the sRGB gamut.
+/// Without, the most transparent color that works, which exists for all targets and
+/// backgrounds in the sRGB gamut.
+pub fn uncomposite(
+    target: OklchA,
+    background: OklchA,
+    space: CompositeSpace,
+    alpha: Option<f32>,
+) -> Option<OklchA> {
+    let [tr, tg, tb, _] = space.to_rgba(target);
+    let [br, bg, bb, _] = space.to_rgba(background);
+    let (target, background) = ([tr, tg, tb], [br, bg, bb]);
+
+    let alpha = match alpha {
+        Some(alpha) => alpha.clamp(0.0, 1.0),
+        // Each channel has to stay within 0..=1: reaching a target above the background takes
+        // at least (t - b) / (1 - b) of white, one below it (b - t) / b of black.
+        None => target
+            .iter()
+            .zip(&background)
+            .map(|(&t, &b)| {
+                if t > b {
+                    (t - b) / (1.0 - b)
+                } else if t < b {
+                    (b - t) / b
+                } else {
+                    0.0
+                }
+            })
+            .fold(0.0, f32::max)
+            .min(1.0),
+    };
+    if alpha <= 0.0 {
+        // The target is the background; any fully transparent color does it.
+        return Some(space.from_rgba([tr, tg, tb, 0.0]));
+    }
+
+    let channel = |t: f32, b: f32| (t - b * (1.0 - alpha)) / alpha;
+    let rgb = [channel(tr, br), channel(tg, bg), channel(tb, bb)];
+    const EPSILON: f32 = 1e-4;
+    if rgb.iter().any(|&v| v < -EPSILON || v > 1.0 + EPSILON) {
+        return None;
+    }
+    let [r, g, b] = rgb;
+    let clamp = |v: f32| v.clamp(0.0, 1.0);
+    Some(space.from_rgba([clamp(r), clamp(g), clamp(b), alpha]))
+}
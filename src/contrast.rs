@@ -0,0 +1,131 @@
+//! Contrast between two colors, and picking a readable text color for a background.
+
+use egui::Color32;
+
+use crate::convert;
+
+/// A way of measuring the contrast of text against its background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContrastMetric {
+    /// The WCAG 2.x contrast ratio, from 1 (none) to 21 (black on white). Symmetric.
+    Wcag2,
+    /// The magnitude of the APCA lightness contrast Lc, from 0 to about 106. Unlike WCAG 2 it
+    /// depends on which color is the text, and doesn't overrate mid-tone pairs.
+    Apca,
+}
+
+impl ContrastMetric {
+    /// How much `text` stands out from `bg`; larger is more. Both are taken as opaque.
+    pub fn contrast(self, text: Color32, bg: Color32) -> f32 {
+        match self {
+            ContrastMetric::Wcag2 => wcag_contrast_ratio(text, bg),
+            ContrastMetric::Apca => apca_contrast(text, bg).abs(),
+        }
+    }
+}
+
+/// A contrast the text color has to reach, see [`best_text_color`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MinContrast {
+    /// A WCAG 2.x contrast ratio, e.g. 4.5 for body text (level AA) or 7 (level AAA).
+    Wcag2(f32),
+    /// An APCA |Lc|, e.g. 75 for body text, 60 for other content text or 45 for large text.
+    ApcaLc(f32),
+}
+
+impl MinContrast {
+    fn metric(self) -> ContrastMetric {
+        match self {
+            MinContrast::Wcag2(_) => ContrastMetric::Wcag2,
+            MinContrast::ApcaLc(_) => ContrastMetric::Apca,
+        }
+    }
+
+    fn threshold(self) -> f32 {
+        match self {
+            MinContrast::Wcag2(ratio) | MinContrast::ApcaLc(ratio) => ratio,
+        }
+    }
+}
+
+/// The WCAG 2.x contrast ratio between `a` and `b`, from 1 to 21.
+pub fn wcag_contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let luminance = |c: Color32| convert::wcag_luminance_srgb8([c.r(), c.g(), c.b()]);
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// The APCA (0.0.98G-4g) lightness contrast Lc of `text` on `bg`: positive for dark text on a
+/// light background, negative for light text on a dark one.
+pub fn apca_contrast(text: Color32, bg: Color32) -> f32 {
+    // Screen luminance with APCA's simple 2.4 power curve and coefficients.
+    let luminance = |c: Color32| {
+        let channel = |v: u8| (v as f32 / 255.0).powf(2.4);
+        let y = 0.212_672_9 * channel(c.r()) + 0.715_152_2 * channel(c.g()) + 0.072_175 * channel(c.b());
+        // Soft clamp near black, for flare.
+        const BLACK_THRESHOLD: f32 = 0.022;
+        if y < BLACK_THRESHOLD {
+            y + (BLACK_THRESHOLD - y).powf(1.414)
+        } else {
+            y
+        }
+    };
+    let (y_text, y_bg) = (luminance(text), luminance(bg));
+    if (y_bg - y_text).abs() < 0.0005 {
+        return 0.0;
+    }
+
+    const SCALE: f32 = 1.14;
+    const LOW_CLIP: f32 = 0.1;
+    const LOW_OFFSET: f32 = 0.027;
+    let lc = if y_bg > y_text {
+        let sapc = (y_bg.powf(0.56) - y_text.powf(0.57)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        let sapc = (y_bg.powf(0.65) - y_text.powf(0.62)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+    lc * 100.0
+}
+
+/// The candidate with the most contrast against `bg`, or black or white if there are none.
+pub fn most_contrasting(bg: Color32, candidates: &[Color32], metric: ContrastMetric) -> Color32 {
+    let candidates = if candidates.is_empty() {
+        &[Color32::BLACK, Color32::WHITE][..]
+    } else {
+        candidates
+    };
+    candidates
+        .iter()
+        .copied()
+        .max_by(|a, b| {
+            metric
+                .contrast(*a, bg)
+                .partial_cmp(&metric.contrast(*b, bg))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(Color32::BLACK)
+}
+
+/// The first of `candidates` (in order of preference, e.g. the theme's text color first) that
+/// reaches `min_contrast` against `bg`. If none do, the one with the most contrast.
+///
+/// ```ignore
+/// let text = best_text_color(bg, &[visuals.text_color(), Color32::WHITE, Color32::BLACK], MinContrast::Wcag2(4.5));
+/// ```
+pub fn best_text_color(bg: Color32, candidates: &[Color32], min_contrast: MinContrast) -> Color32 {
+    let metric = min_contrast.metric();
+    candidates
+        .iter()
+        .copied()
+        .find(|&text| metric.contrast(text, bg) >= min_contrast.threshold())
+        .unwrap_or_else(|| most_contrasting(bg, candidates, metric))
+}
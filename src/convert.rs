@@ -0,0 +1,473 @@
+//! Small pure conversion and gamut helpers, usable without any UI.
+
+use std::ops::RangeInclusive;
+
+use colstodian::*;
+use egui::{Color32, Rgba};
+
+use cint::ColorInterop;
+
+use crate::{EncodedSrgbA, LinearSrgbA, OklabA, OklchA, Srgba};
+
+mod batch;
+pub mod interop;
+pub mod plain;
+pub use batch::{oklch_to_srgb8_batch, oklch_to_srgba_batch};
+
+/// Colors within this distance outside of `0..=1` in linear sRGB still count as in gamut,
+/// to absorb rounding error in the conversions.
+const GAMUT_EPSILON: f32 = 1e-4;
+
+/// The unit hues are shown and entered in. They are always stored in radians.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HueUnit {
+    /// `0..360`.
+    Degrees,
+    /// `0..2π`.
+    Radians,
+    /// `0..1`.
+    Turns,
+}
+
+impl Default for HueUnit {
+    fn default() -> Self {
+        HueUnit::Degrees
+    }
+}
+
+impl HueUnit {
+    /// One full turn in this unit.
+    pub fn full_turn(self) -> f32 {
+        match self {
+            HueUnit::Degrees => 360.0,
+            HueUnit::Radians => std::f32::consts::TAU,
+            HueUnit::Turns => 1.0,
+        }
+    }
+
+    /// The hue `h` in radians (in any range) in this unit, in `0..full_turn()`.
+    pub fn from_radians(self, h: f32) -> f32 {
+        (h / std::f32::consts::TAU).rem_euclid(1.0) * self.full_turn()
+    }
+
+    /// The hue `value` in this unit in radians, in `-π..π`.
+    pub fn to_radians(self, value: f32) -> f32 {
+        wrap_hue(value / self.full_turn() * std::f32::consts::TAU)
+    }
+
+    /// The suffix shown after values in this unit.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            HueUnit::Degrees => "°",
+            HueUnit::Radians => " rad",
+            HueUnit::Turns => " turn",
+        }
+    }
+
+    /// The CSS unit for this unit, as accepted by [`from_css_oklch`].
+    pub fn css_unit(self) -> &'static str {
+        match self {
+            HueUnit::Degrees => "deg",
+            HueUnit::Radians => "rad",
+            HueUnit::Turns => "turn",
+        }
+    }
+}
+
+/// How the picker shows numbers, in its readouts, its number fields and copied text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Decimal places of values around `0..=1`. Percentages and degrees get two fewer, so that
+    /// all show about the same precision.
+    pub decimals: usize,
+    /// Show lightness and alpha as percentages instead of `0..=1`.
+    pub percent: bool,
+    /// Show sRGB channels as `0..=1` floats instead of `0..=255` integers.
+    pub srgb_floats: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimals: 3,
+            percent: true,
+            srgb_floats: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Decimal places of values around `0..=100` or `0..=360`.
+    pub fn scaled_decimals(&self) -> usize {
+        self.decimals.saturating_sub(2)
+    }
+
+    /// `value` with [`Self::decimals`] places.
+    pub fn number(&self, value: f32) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+
+    /// A lightness or alpha `value`, as a percentage if [`Self::percent`].
+    pub fn unit_interval(&self, value: f32) -> String {
+        if self.percent {
+            format!("{:.*}%", self.scaled_decimals(), value * 100.0)
+        } else {
+            self.number(value)
+        }
+    }
+
+    /// An encoded sRGB channel, as an integer or as a float if [`Self::srgb_floats`].
+    pub fn srgb_channel(&self, value: u8) -> String {
+        if self.srgb_floats {
+            self.number(value as f32 / 255.0)
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// Wraps a hue in radians into `-π..π`, the range the hue slider uses.
+pub(crate) fn wrap_hue(h: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (h + PI).rem_euclid(TAU) - PI
+}
+
+/// Oklch to encoded sRGB bytes with premultiplied alpha (the same layout as [`Color32`]),
+/// clipping out-of-gamut colors.
+pub fn oklch_to_srgb8(color: OklchA) -> [u8; 4] {
+    color.convert::<Srgba>().saturate().to_u8()
+}
+
+/// How [`oklch_to_srgb8_dithered`] spreads the rounding error of quantizing to 8 bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dither {
+    /// Plain rounding. Smooth, dark or near-neutral ramps show visible bands.
+    None,
+    /// An 8×8 Bayer matrix. Cheap and stable, but with a faint regular pattern.
+    Ordered,
+    /// Interleaved gradient noise, a blue-noise-like pattern without a lookup texture.
+    Noise,
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Dither::None
+    }
+}
+
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+impl Dither {
+    /// The rounding threshold in `0..1` for the pixel at `x`, `y`.
+    fn threshold(self, x: usize, y: usize) -> f32 {
+        match self {
+            Dither::None => 0.5,
+            Dither::Ordered => (BAYER_8X8[y % 8][x % 8] as f32 + 0.5) / 64.0,
+            Dither::Noise => {
+                // Jorge Jimenez, "Next Generation Post Processing in Call of Duty: Advanced Warfare".
+                let (x, y) = (x as f32, y as f32);
+                (52.982_918 * (0.067_110_56 * x + 0.005_837_15 * y).fract()).fract()
+            }
+        }
+    }
+}
+
+/// Like [`oklch_to_srgb8`], but rounds up or down depending on the pixel position `x`, `y`
+/// according to `dither`, so that large smooth areas written to an 8-bit texture don't band.
+pub fn oklch_to_srgb8_dithered(color: OklchA, x: usize, y: usize, dither: Dither) -> [u8; 4] {
+    let srgba = color.convert::<Srgba>().saturate();
+    dither_srgba([srgba.col.r, srgba.col.g, srgba.col.b, srgba.alpha], x, y, dither)
+}
+
+/// Quantizes premultiplied encoded sRGB in `0..=1` to bytes, as [`oklch_to_srgb8_dithered`].
+pub(crate) fn dither_srgba(srgba: [f32; 4], x: usize, y: usize, dither: Dither) -> [u8; 4] {
+    quantize_srgba(srgba, dither.threshold(x, y))
+}
+
+/// Rounds up where the fractional part is at least `threshold` (0.5 for plain rounding).
+pub(crate) fn quantize_srgba([r, g, b, a]: [f32; 4], threshold: f32) -> [u8; 4] {
+    let quantize = |v: f32| (v * 255.0 + (1.0 - threshold)).floor().clamp(0.0, 255.0) as u8;
+    let alpha = quantize(a);
+    // Keep the premultiplied invariant: no channel may exceed alpha.
+    let channel = |v: f32| quantize(v).min(alpha);
+    [channel(r), channel(g), channel(b), alpha]
+}
+
+/// How the [`Color32`]s of color previews are encoded, for displays whose compositor doesn't
+/// expect sRGB. Set it for a whole app with [`set_output_transform`](crate::set_output_transform).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputTransform {
+    /// Plain sRGB, what egui and most compositors expect.
+    Srgb,
+    /// Display P3 primaries with the sRGB transfer function, for compositors that hand colors to
+    /// a wide-gamut display unconverted and so show sRGB colors over-saturated.
+    DisplayP3,
+    /// sRGB primaries with a pure power-law transfer function of this exponent, e.g. 2.2 for
+    /// displays calibrated to gamma 2.2 rather than the sRGB curve.
+    Gamma(f32),
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        OutputTransform::Srgb
+    }
+}
+
+/// Linear sRGB to linear Display P3 (both D65).
+const LINEAR_SRGB_TO_LINEAR_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+impl OutputTransform {
+    /// Re-encodes the sRGB `color` for the display, keeping its alpha.
+    pub fn apply(self, color: Color32) -> Color32 {
+        let rgba = Rgba::from(color);
+        let alpha = rgba.a();
+        if self == OutputTransform::Srgb || alpha <= 0.0 {
+            return color;
+        }
+        let rgb = [rgba.r() / alpha, rgba.g() / alpha, rgba.b() / alpha];
+        // `Color32::from(Rgba)` applies the sRGB transfer function, so other transfer functions
+        // are expressed as the linear values it encodes to the wanted ones.
+        let [r, g, b] = match self {
+            OutputTransform::Srgb => rgb,
+            OutputTransform::DisplayP3 => plain::mul(&LINEAR_SRGB_TO_LINEAR_P3, rgb),
+            OutputTransform::Gamma(gamma) => {
+                let gamma = gamma.max(0.01);
+                let [r, g, b] = rgb;
+                let encode = |v: f32| plain::decode_srgb(v.max(0.0).powf(1.0 / gamma));
+                [encode(r), encode(g), encode(b)]
+            }
+        };
+        let premultiply = |v: f32| v.clamp(0.0, 1.0) * alpha;
+        Color32::from(Rgba::from_rgba_premultiplied(premultiply(r), premultiply(g), premultiply(b), alpha))
+    }
+}
+
+/// Encoded sRGB bytes with premultiplied alpha (the same layout as [`Color32`]) to Oklch.
+pub fn srgb8_to_oklch([r, g, b, a]: [u8; 4]) -> OklchA {
+    let srgba = Srgba::from(Color32::from_rgba_premultiplied(r, g, b, a).into_cint());
+    srgba.convert()
+}
+
+/// Linearly interpolates between `a` and `b` in Oklab (including alpha).
+/// Unlike interpolating Oklch, this never sweeps through unrelated hues.
+pub fn oklab_lerp(a: OklchA, b: OklchA, t: f32) -> OklchA {
+    let a: OklabA = a.convert();
+    let b: OklabA = b.convert();
+    let lerp = |x: f32, y: f32| x + (y - x) * t;
+
+    let mut out = a;
+    out.col.l = lerp(a.col.l, b.col.l);
+    out.col.a = lerp(a.col.a, b.col.a);
+    out.col.b = lerp(a.col.b, b.col.b);
+    out.alpha = lerp(a.alpha, b.alpha);
+    out.convert()
+}
+
+/// Euclidean distance between `a` and `b` in Oklab (ignoring alpha), a simple and fairly
+/// uniform perceptual color difference. A just-noticeable difference is around 0.02.
+pub fn delta_e_ok(a: OklchA, b: OklchA) -> f32 {
+    let a: OklabA = a.convert();
+    let b: OklabA = b.convert();
+    let (dl, da, db) = (a.col.l - b.col.l, a.col.a - b.col.a, a.col.b - b.col.b);
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+/// Whether `color` can be displayed in sRGB without clipping.
+pub fn is_in_srgb_gamut(color: OklchA) -> bool {
+    let lin: LinearSrgbA = color.convert();
+    let range = -GAMUT_EPSILON..=1.0 + GAMUT_EPSILON;
+    range.contains(&lin.col.r) && range.contains(&lin.col.g) && range.contains(&lin.col.b)
+}
+
+/// The largest Oklch chroma at lightness `l` and hue `h` (in radians) that is still
+/// inside the sRGB gamut.
+pub fn max_chroma_for(l: f32, h: f32) -> f32 {
+    let mut color = OklchA::new(l, 0.0, h, 1.0);
+    if !is_in_srgb_gamut(color) {
+        return 0.0;
+    }
+
+    // The boundary is well-behaved along constant hue and lightness, so bisection is enough.
+    let (mut lo, mut hi) = (0.0, 0.5);
+    for _ in 0..20 {
+        color.col.c = (lo + hi) / 2.0;
+        if is_in_srgb_gamut(color) {
+            lo = color.col.c;
+        } else {
+            hi = color.col.c;
+        }
+    }
+    lo
+}
+
+/// The lightnesses at which Oklch chroma `c` and hue `h` are inside the sRGB gamut, or `None`
+/// if `c` is out of gamut at every lightness.
+pub fn in_gamut_lightness_range(c: f32, h: f32) -> Option<RangeInclusive<f32>> {
+    const STEPS: usize = 64;
+    let in_gamut = |l: f32| is_in_srgb_gamut(OklchA::new(l, c, h, 1.0));
+    let l_at = |i: usize| i as f32 / STEPS as f32;
+
+    // At constant chroma and hue the in-gamut lightnesses form one interval, around the cusp.
+    let first = (0..=STEPS).find(|&i| in_gamut(l_at(i)))?;
+    let last = (first..=STEPS).rev().find(|&i| in_gamut(l_at(i)))?;
+    let edge = |mut inside: f32, mut outside: f32| {
+        for _ in 0..16 {
+            let mid = (inside + outside) / 2.0;
+            if in_gamut(mid) {
+                inside = mid;
+            } else {
+                outside = mid;
+            }
+        }
+        inside
+    };
+    let min = if first == 0 { 0.0 } else { edge(l_at(first), l_at(first - 1)) };
+    let max = if last == STEPS { 1.0 } else { edge(l_at(last), l_at(last + 1)) };
+    Some(min..=max)
+}
+
+/// `color` with its Oklch lightness raised by `dl` (clamped to `0..=1`), keeping chroma and hue.
+pub fn lighten(color: OklchA, dl: f32) -> OklchA {
+    let mut color = color;
+    color.col.l = (color.col.l + dl).clamp(0.0, 1.0);
+    color
+}
+
+/// [`lighten`] by `-dl`.
+pub fn darken(color: OklchA, dl: f32) -> OklchA {
+    lighten(color, -dl)
+}
+
+/// `color` with its Oklch chroma raised by `dc` (never below 0), keeping lightness and hue. The
+/// result may be outside the sRGB gamut, see [`max_chroma_for`].
+pub fn saturate(color: OklchA, dc: f32) -> OklchA {
+    let mut color = color;
+    color.col.c = (color.col.c + dc).max(0.0);
+    color
+}
+
+/// [`saturate`] by `-dc`.
+pub fn desaturate(color: OklchA, dc: f32) -> OklchA {
+    saturate(color, -dc)
+}
+
+/// CIE 1931 luminance Y of the color in sRGB (D65 white = 1), ignoring alpha. Out-of-gamut
+/// colors are clipped first, as they would be on screen.
+pub fn cie_luminance(color: OklchA) -> f32 {
+    let lin: LinearSrgbA = color.convert();
+    let clip = |v: f32| v.clamp(0.0, 1.0);
+    0.2126 * clip(lin.col.r) + 0.7152 * clip(lin.col.g) + 0.0722 * clip(lin.col.b)
+}
+
+/// Relative luminance as defined by WCAG 2 (for contrast ratios), ignoring alpha. Unlike
+/// [`cie_luminance`] it linearizes the 8-bit sRGB values with WCAG's slightly different
+/// threshold of 0.03928, so it matches what accessibility checkers report.
+pub fn wcag_relative_luminance(color: OklchA) -> f32 {
+    let [r, g, b, _] = color.convert::<EncodedSrgbA>().saturate().to_u8();
+    wcag_luminance_srgb8([r, g, b])
+}
+
+/// [`wcag_relative_luminance`] of encoded sRGB bytes.
+pub(crate) fn wcag_luminance_srgb8([r, g, b]: [u8; 3]) -> f32 {
+    let linear = |v: u8| {
+        let v = v as f32 / 255.0;
+        if v <= 0.03928 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// `#rrggbb`, or `#rrggbbaa` if not opaque, with straight (not premultiplied) alpha.
+pub fn to_hex(color: OklchA) -> String {
+    let [r, g, b, a] = color.convert::<EncodedSrgbA>().saturate().to_u8();
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+/// CSS Color 4 `oklch()` notation, e.g. `oklch(62.8% 0.2577 29.2)`, with ` / alpha` if not opaque.
+pub fn to_css_oklch(color: OklchA) -> String {
+    let l = color.col.l * 100.0;
+    let h = color.col.h.to_degrees().rem_euclid(360.0);
+    if color.alpha < 1.0 {
+        format!("oklch({:.1}% {:.4} {:.1} / {:.3})", l, color.col.c, h, color.alpha)
+    } else {
+        format!("oklch({:.1}% {:.4} {:.1})", l, color.col.c, h)
+    }
+}
+
+/// Like [`to_css_oklch`], with the hue in `unit` and the numbers as `format` says. Degrees are
+/// written without a unit, as CSS assumes them.
+pub fn to_css_oklch_in(color: OklchA, unit: HueUnit, format: &NumberFormat) -> String {
+    let hue = unit.from_radians(color.col.h);
+    let h = match unit {
+        HueUnit::Degrees => format!("{:.*}", format.scaled_decimals(), hue),
+        _ => format!("{:.*}{}", format.decimals, hue, unit.css_unit()),
+    };
+    let l = format.unit_interval(color.col.l);
+    let c = format.number(color.col.c);
+    if color.alpha < 1.0 {
+        format!("oklch({} {} {} / {})", l, c, h, format.unit_interval(color.alpha))
+    } else {
+        format!("oklch({} {} {})", l, c, h)
+    }
+}
+
+/// Parses CSS Color 4 `oklch()` notation. Lightness and alpha may be numbers or percentages,
+/// hue may have a `deg`, `rad` or `turn` unit (degrees if none).
+pub fn from_css_oklch(css: &str) -> Option<OklchA> {
+    let inner = css.trim().strip_prefix("oklch(")?.strip_suffix(')')?;
+    let (channels, alpha) = match inner.find('/') {
+        Some(slash) => (&inner[..slash], Some(inner[slash + 1..].trim())),
+        None => (inner, None),
+    };
+
+    let number_or_percent = |s: &str, percent_scale: f32| -> Option<f32> {
+        match s.strip_suffix('%') {
+            Some(percent) => percent.parse::<f32>().ok().map(|p| p / 100.0 * percent_scale),
+            None => s.parse().ok(),
+        }
+    };
+
+    let mut channels = channels.split_whitespace();
+    let l = number_or_percent(channels.next()?, 1.0)?;
+    // In CSS, 100% chroma is 0.4.
+    let c = number_or_percent(channels.next()?, 0.4)?;
+    let h = channels.next()?;
+    let h = if let Some(deg) = h.strip_suffix("deg") {
+        deg.parse::<f32>().ok()?.to_radians()
+    } else if let Some(rad) = h.strip_suffix("rad") {
+        rad.parse::<f32>().ok()?
+    } else if let Some(turn) = h.strip_suffix("turn") {
+        turn.parse::<f32>().ok()? * std::f32::consts::TAU
+    } else {
+        h.parse::<f32>().ok()?.to_radians()
+    };
+    if channels.next().is_some() {
+        return None;
+    }
+    let alpha = match alpha {
+        Some(alpha) => number_or_percent(alpha, 1.0)?,
+        None => 1.0,
+    };
+
+    Some(OklchA::new(l, c, wrap_hue(h), alpha))
+}
@@ -0,0 +1,62 @@
+//! Batched Oklch to sRGB conversion, for filling gradients and lookup tables.
+//!
+//! Same math as going through colstodian one color at a time, but laid out as fixed-size
+//! chunks of plain `f32` arrays, which the compiler vectorizes.
+
+use super::plain::{encode_srgb, LAB_TO_LMS, LMS_TO_LINEAR_SRGB};
+use crate::OklchA;
+
+const LANES: usize = 8;
+
+/// Converts one chunk, writing encoded sRGB with premultiplied alpha, clamped to `0..=1`.
+fn convert_chunk(colors: &[OklchA], out: &mut [[f32; 4]]) {
+    let mut l = [0.0; LANES];
+    let mut a = [0.0; LANES];
+    let mut b = [0.0; LANES];
+    let mut alpha = [0.0; LANES];
+    for (i, color) in colors.iter().enumerate() {
+        let (sin, cos) = color.col.h.sin_cos();
+        l[i] = color.col.l;
+        a[i] = color.col.c * cos;
+        b[i] = color.col.c * sin;
+        alpha[i] = color.alpha.clamp(0.0, 1.0);
+    }
+
+    let mut lms = [[0.0; LANES]; 3];
+    for (row, m) in lms.iter_mut().zip(&LAB_TO_LMS) {
+        for i in 0..LANES {
+            let v = m[0] * l[i] + m[1] * a[i] + m[2] * b[i];
+            row[i] = v * v * v;
+        }
+    }
+
+    let mut rgb = [[0.0; LANES]; 3];
+    for (row, m) in rgb.iter_mut().zip(&LMS_TO_LINEAR_SRGB) {
+        for i in 0..LANES {
+            row[i] = m[0] * lms[0][i] + m[1] * lms[1][i] + m[2] * lms[2][i];
+        }
+    }
+
+    for (i, out) in out.iter_mut().enumerate() {
+        let channel = |c: usize| encode_srgb(rgb[c][i].clamp(0.0, 1.0)) * alpha[i];
+        *out = [channel(0), channel(1), channel(2), alpha[i]];
+    }
+}
+
+/// Like converting each color to [`Srgba`](crate::Srgba) and saturating it: encoded sRGB with
+/// premultiplied alpha, clipped to `0..=1`, as `[r, g, b, a]`.
+pub fn oklch_to_srgba_batch(colors: &[OklchA]) -> Vec<[f32; 4]> {
+    let mut out = vec![[0.0; 4]; colors.len()];
+    for (colors, out) in colors.chunks(LANES).zip(out.chunks_mut(LANES)) {
+        convert_chunk(colors, out);
+    }
+    out
+}
+
+/// Batched [`oklch_to_srgb8`](super::oklch_to_srgb8).
+pub fn oklch_to_srgb8_batch(colors: &[OklchA]) -> Vec<[u8; 4]> {
+    oklch_to_srgba_batch(colors)
+        .into_iter()
+        .map(|srgba| super::quantize_srgba(srgba, 0.5))
+        .collect()
+}
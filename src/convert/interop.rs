@@ -0,0 +1,56 @@
+//! Conversions to and from the color types of other crates, each behind the feature of the
+//! same name. These are functions rather than `From` impls since [`OklchA`] is colstodian's
+//! type, which this crate can't implement foreign traits for.
+
+#[allow(unused_imports)]
+use colstodian::*;
+
+#[allow(unused_imports)]
+use crate::{EncodedSrgbA, OklchA};
+
+/// [`OklchA`] to `palette::Oklcha` (hue in degrees there).
+#[cfg(feature = "palette_color")]
+pub fn to_palette(color: OklchA) -> palette_color::Oklcha {
+    palette_color::Oklcha::new(color.col.l, color.col.c, color.col.h.to_degrees(), color.alpha)
+}
+
+#[cfg(feature = "palette_color")]
+pub fn from_palette(color: palette_color::Oklcha) -> OklchA {
+    OklchA::new(
+        color.color.l,
+        color.color.chroma,
+        super::wrap_hue(color.color.hue.into_radians()),
+        color.alpha,
+    )
+}
+
+/// [`OklchA`] to `csscolorparser::Color`, which is encoded sRGB with straight alpha. Colors out
+/// of the sRGB gamut are clipped.
+#[cfg(feature = "csscolorparser")]
+pub fn to_css_color(color: OklchA) -> csscolorparser::Color {
+    let srgb: EncodedSrgbA = color.convert();
+    let srgb = srgb.saturate();
+    csscolorparser::Color::new(srgb.col.r as f64, srgb.col.g as f64, srgb.col.b as f64, srgb.alpha as f64)
+}
+
+#[cfg(feature = "csscolorparser")]
+pub fn from_css_color(color: &csscolorparser::Color) -> OklchA {
+    EncodedSrgbA::new(color.r as f32, color.g as f32, color.b as f32, color.a as f32).convert()
+}
+
+/// [`OklchA`] to `bevy_color::Oklcha` (hue in degrees, `0..360`, there).
+#[cfg(feature = "bevy_color")]
+pub fn to_bevy(color: OklchA) -> bevy_color::Oklcha {
+    let hue = color.col.h.to_degrees().rem_euclid(360.0);
+    bevy_color::Oklcha::new(color.col.l, color.col.c, hue, color.alpha)
+}
+
+#[cfg(feature = "bevy_color")]
+pub fn from_bevy(color: bevy_color::Oklcha) -> OklchA {
+    OklchA::new(
+        color.lightness,
+        color.chroma,
+        super::wrap_hue(color.hue.to_radians()),
+        color.alpha,
+    )
+}
@@ -0,0 +1,97 @@
+//! Self-contained Oklab/Oklch ⇄ sRGB math on plain arrays, without going through colstodian's
+//! types. Handy at API boundaries that don't want to name `ColorAlpha`, and the reference for
+//! the batched conversions. Hues are in radians, like [`OklchA`](crate::OklchA).
+
+pub(crate) const LINEAR_SRGB_TO_LMS: [[f32; 3]; 3] = [
+    [0.412_221_47, 0.536_332_55, 0.051_445_995],
+    [0.211_903_5, 0.680_699_5, 0.107_396_96],
+    [0.088_302_46, 0.281_718_85, 0.629_978_7],
+];
+
+pub(crate) const LMS_TO_LAB: [[f32; 3]; 3] = [
+    [0.210_454_26, 0.793_617_8, -0.004_072_047],
+    [1.977_998_5, -2.428_592_2, 0.450_593_7],
+    [0.025_904_037, 0.782_771_77, -0.808_675_77],
+];
+
+pub(crate) const LAB_TO_LMS: [[f32; 3]; 3] = [
+    [1.0, 0.396_337_78, 0.215_803_76],
+    [1.0, -0.105_561_346, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_485_5],
+];
+
+pub(crate) const LMS_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [4.076_741_7, -3.307_711_6, 0.230_969_94],
+    [-1.268_438, 2.609_757_4, -0.341_319_38],
+    [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+];
+
+pub(crate) fn mul(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// The sRGB transfer function, from linear light to encoded values.
+pub fn encode_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Inverse of [`encode_srgb`].
+pub fn decode_srgb(encoded: f32) -> f32 {
+    if encoded <= 0.040_45 {
+        encoded / 12.92
+    } else {
+        ((encoded + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn oklab_from_linear_srgb(rgb: [f32; 3]) -> [f32; 3] {
+    let [l, m, s] = mul(&LINEAR_SRGB_TO_LMS, rgb);
+    mul(&LMS_TO_LAB, [l.cbrt(), m.cbrt(), s.cbrt()])
+}
+
+/// The result is out of `0..=1` for colors outside the sRGB gamut.
+pub fn linear_srgb_from_oklab(lab: [f32; 3]) -> [f32; 3] {
+    let [l, m, s] = mul(&LAB_TO_LMS, lab);
+    mul(&LMS_TO_LINEAR_SRGB, [l * l * l, m * m * m, s * s * s])
+}
+
+pub fn oklch_from_oklab([l, a, b]: [f32; 3]) -> [f32; 3] {
+    [l, a.hypot(b), b.atan2(a)]
+}
+
+pub fn oklab_from_oklch([l, c, h]: [f32; 3]) -> [f32; 3] {
+    let (sin, cos) = h.sin_cos();
+    [l, c * cos, c * sin]
+}
+
+/// Oklch `[l, c, h, alpha]` to encoded sRGB bytes with premultiplied alpha (the layout of
+/// [`Color32`](egui::Color32)), clipping out-of-gamut colors.
+pub fn srgb8_from_oklch([l, c, h, alpha]: [f32; 4]) -> [u8; 4] {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let rgb = linear_srgb_from_oklab(oklab_from_oklch([l, c, h]));
+    let byte = |v: f32| (v * 255.0).round() as u8;
+    let channel = |v: f32| byte(encode_srgb(v.clamp(0.0, 1.0)) * alpha);
+    [channel(rgb[0]), channel(rgb[1]), channel(rgb[2]), byte(alpha)]
+}
+
+/// Inverse of [`srgb8_from_oklch`]. Fully transparent colors come out black.
+pub fn oklch_from_srgb8([r, g, b, a]: [u8; 4]) -> [f32; 4] {
+    let alpha = a as f32 / 255.0;
+    let channel = |v: u8| {
+        if a == 0 {
+            0.0
+        } else {
+            decode_srgb(v as f32 / 255.0 / alpha)
+        }
+    };
+    let [l, c, h] = oklch_from_oklab(oklab_from_linear_srgb([channel(r), channel(g), channel(b)]));
+    [l, c, h, alpha]
+}
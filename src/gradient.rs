@@ -0,0 +1,352 @@
+//! Multi-stop color gradients, interpolated perceptually, and their export to textures and CSS.
+
+use std::f32::consts::TAU;
+
+use colstodian::*;
+
+use crate::{convert, EncodedSrgbA, LinearSrgbA, OklchA};
+
+mod colormaps;
+mod editor;
+pub use colormaps::{colormap, Colormap};
+pub use editor::{gradient_edit, gradient_edit_keyed, KeyedGradientResponse, StopChange};
+
+/// The space a [`Gradient`] interpolates between its stops in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterpolationSpace {
+    /// Straight lines in Oklab. Perceptually even, and never sweeps through unrelated hues.
+    Oklab,
+    /// Oklch, going the short way around the hue circle.
+    OklchShorter,
+    /// Oklch, going the long way around the hue circle (rainbows).
+    OklchLonger,
+    /// Linear sRGB, i.e. physically mixing light.
+    LinearSrgb,
+    /// Encoded sRGB, which is what browsers and most image editors do by default.
+    EncodedSrgb,
+}
+
+impl Default for InterpolationSpace {
+    fn default() -> Self {
+        InterpolationSpace::Oklab
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        lerp(a[0], b[0], t),
+        lerp(a[1], b[1], t),
+        lerp(a[2], b[2], t),
+        lerp(a[3], b[3], t),
+    ]
+}
+
+impl InterpolationSpace {
+    /// The color `t` of the way from `a` to `b` in this space.
+    pub fn interpolate(self, a: OklchA, b: OklchA, t: f32) -> OklchA {
+        match self {
+            InterpolationSpace::Oklab => convert::oklab_lerp(a, b, t),
+            InterpolationSpace::OklchShorter | InterpolationSpace::OklchLonger => {
+                // Hue difference in -π..=π, then pushed the other way around if going long.
+                let mut dh = convert::wrap_hue(b.col.h - a.col.h);
+                if self == InterpolationSpace::OklchLonger {
+                    dh -= dh.signum() * TAU;
+                    if dh == 0.0 {
+                        dh = TAU;
+                    }
+                }
+                let h = convert::wrap_hue(a.col.h + dh * t);
+                OklchA::new(
+                    lerp(a.col.l, b.col.l, t),
+                    lerp(a.col.c, b.col.c, t),
+                    h,
+                    lerp(a.alpha, b.alpha, t),
+                )
+            }
+            InterpolationSpace::LinearSrgb => {
+                let (a, b): (LinearSrgbA, LinearSrgbA) = (a.convert(), b.convert());
+                let [r, g, b, alpha] = lerp4(
+                    [a.col.r, a.col.g, a.col.b, a.alpha],
+                    [b.col.r, b.col.g, b.col.b, b.alpha],
+                    t,
+                );
+                LinearSrgbA::new(r, g, b, alpha).convert()
+            }
+            InterpolationSpace::EncodedSrgb => {
+                let (a, b): (EncodedSrgbA, EncodedSrgbA) = (a.convert(), b.convert());
+                let [r, g, b, alpha] = lerp4(
+                    [a.col.r, a.col.g, a.col.b, a.alpha],
+                    [b.col.r, b.col.g, b.col.b, b.alpha],
+                    t,
+                );
+                EncodedSrgbA::new(r, g, b, alpha).convert()
+            }
+        }
+    }
+}
+
+/// How the interpolation from one stop to the next is paced.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    Linear,
+    Smoothstep,
+    /// A CSS-style `cubic-bezier(x1, y1, x2, y2)` curve from (0, 0) to (1, 1).
+    /// `x1` and `x2` must be in `0..=1`.
+    CubicBezier([f32; 4]),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+/// One coordinate of a cubic bezier from 0 to 1 with control points `p1` and `p2`.
+fn bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+impl Easing {
+    /// Remaps the progress `t` (in `0..=1`) between two stops.
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicBezier([x1, y1, x2, y2]) => {
+                // x(s) is monotonic for x1, x2 in 0..=1, so bisect for the s where x(s) = t.
+                let (mut lo, mut hi) = (0.0, 1.0);
+                for _ in 0..24 {
+                    let mid = (lo + hi) / 2.0;
+                    if bezier(x1, x2, mid) < t {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                bezier(y1, y2, (lo + hi) / 2.0)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GradientStop {
+    /// Position along the gradient, in `0..=1`.
+    pub t: f32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_oklch"))]
+    pub color: OklchA,
+    /// Easing of the segment from this stop to the next.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub easing: Easing,
+}
+
+impl GradientStop {
+    pub fn new(t: f32, color: OklchA) -> Self {
+        Self {
+            t,
+            color,
+            easing: Easing::default(),
+        }
+    }
+}
+
+/// Color stops interpolated in a chosen [`InterpolationSpace`] (Oklab by default).
+/// `stops` are expected to be sorted by `t`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gradient {
+    pub stops: Vec<GradientStop>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub space: InterpolationSpace,
+}
+
+impl Gradient {
+    /// A gradient with the given stops, sorted by position.
+    pub fn new(mut stops: Vec<GradientStop>) -> Self {
+        stops.sort_by(|a, b| a.t.partial_cmp(&b.t).unwrap_or(std::cmp::Ordering::Equal));
+        Self {
+            stops,
+            space: InterpolationSpace::default(),
+        }
+    }
+
+    /// Which space to interpolate between stops in.
+    pub fn with_space(mut self, space: InterpolationSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Evenly spaced stops with the given colors.
+    pub fn from_colors(colors: &[OklchA]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| GradientStop::new(i as f32 / last, color))
+                .collect(),
+        )
+    }
+
+    /// The color at `t`, clamping to the first and last stops outside of them.
+    /// An empty gradient is transparent black.
+    pub fn sample(&self, t: f32) -> OklchA {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return OklchA::new(0.0, 0.0, 0.0, 0.0),
+        };
+        if t <= first.t {
+            return first.color;
+        }
+        if t >= last.t {
+            return last.color;
+        }
+
+        let next = self.stops.iter().position(|stop| stop.t > t).unwrap_or(self.stops.len() - 1);
+        let (a, b) = (&self.stops[next - 1], &self.stops[next]);
+        let local_t = if b.t > a.t { (t - a.t) / (b.t - a.t) } else { 0.0 };
+        self.space.interpolate(a.color, b.color, a.easing.apply(local_t))
+    }
+
+    /// `n` evenly spaced samples from `t = 0` to `t = 1` inclusive.
+    pub fn sample_many(&self, n: usize) -> Vec<OklchA> {
+        let last = n.saturating_sub(1).max(1) as f32;
+        (0..n).map(|i| self.sample(i as f32 / last)).collect()
+    }
+
+    /// `n` evenly spaced samples as encoded sRGB bytes with premultiplied alpha (like
+    /// [`Color32`](egui::Color32)), e.g. to upload as a 1D lookup texture.
+    pub fn to_lut(&self, n: usize) -> Vec<[u8; 4]> {
+        convert::oklch_to_srgb8_batch(&self.sample_many(n))
+    }
+
+    /// Same as [`Gradient::to_lut`], flattened to raw RGBA bytes.
+    pub fn to_lut_bytes(&self, n: usize) -> Vec<u8> {
+        self.to_lut(n).into_iter().flatten().collect()
+    }
+
+    /// A `width` × `height` image of the gradient running left to right, row by row, in the
+    /// same byte layout as [`Gradient::to_lut`]. With a [`Dither`](convert::Dither) other than
+    /// `None`, rows differ slightly so that the 8-bit result doesn't show banding.
+    pub fn to_image(&self, width: usize, height: usize, dither: convert::Dither) -> Vec<[u8; 4]> {
+        profile_function!();
+        // Every row has the same colors, so convert them once and only dither per pixel.
+        let samples = convert::oklch_to_srgba_batch(&self.sample_many(width));
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for (x, srgba) in samples.iter().enumerate() {
+                pixels.push(convert::dither_srgba(*srgba, x, y, dither));
+            }
+        }
+        pixels
+    }
+
+    /// A CSS `linear-gradient(...)` at `angle_degrees`. Browsers interpolate in sRGB, so
+    /// `steps_per_segment - 1` intermediate stops are baked in between each pair of stops to
+    /// approximate the gradient's own interpolation.
+    pub fn to_css_linear_gradient(&self, angle_degrees: f32, steps_per_segment: usize) -> String {
+        let steps = steps_per_segment.max(1);
+        let mut css_stops = Vec::new();
+        for pair in self.stops.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            // Each segment includes its start; the very last stop is added after the loop.
+            for step in 0..steps {
+                let t = a.t + (b.t - a.t) * step as f32 / steps as f32;
+                let color = self.space.interpolate(a.color, b.color, a.easing.apply(step as f32 / steps as f32));
+                css_stops.push(format!("{} {:.2}%", convert::to_hex(color), t * 100.0));
+            }
+        }
+        if let Some(last) = self.stops.last() {
+            css_stops.push(format!("{} {:.2}%", convert::to_hex(last.color), last.t * 100.0));
+        }
+        format!("linear-gradient({}deg, {})", angle_degrees, css_stops.join(", "))
+    }
+}
+
+/// A gradient whose stops are bound to application keys (enum variants, ids, ...), e.g. one
+/// stop per severity level, so that edits can be mapped back to what each stop stands for.
+/// Edit it with [`gradient_edit_keyed`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyedGradient<K> {
+    /// `(key, stop)` pairs, expected to be sorted by `t`.
+    pub stops: Vec<(K, GradientStop)>,
+    pub space: InterpolationSpace,
+}
+
+impl<K: Clone> KeyedGradient<K> {
+    /// A keyed gradient with the given stops, sorted by position.
+    pub fn new(mut stops: Vec<(K, GradientStop)>) -> Self {
+        stops.sort_by(|(_, a), (_, b)| a.t.partial_cmp(&b.t).unwrap_or(std::cmp::Ordering::Equal));
+        Self {
+            stops,
+            space: InterpolationSpace::default(),
+        }
+    }
+
+    /// The stop bound to `key`, if any.
+    pub fn stop(&self, key: &K) -> Option<&GradientStop>
+    where
+        K: PartialEq,
+    {
+        self.stops.iter().find(|(k, _)| k == key).map(|(_, stop)| stop)
+    }
+
+    /// The stops without their keys, for sampling and export.
+    pub fn gradient(&self) -> Gradient {
+        Gradient {
+            stops: self.stops.iter().map(|(_, stop)| stop.clone()).collect(),
+            space: self.space,
+        }
+    }
+}
+
+/// Keeps a [`Gradient::to_image`] at the physical pixel size it is displayed at, for callers
+/// that draw gradients from a texture. The widgets in this crate paint meshes instead, which
+/// stay crisp at any scale on their own.
+///
+/// ```ignore
+/// if let Some((size, pixels)) = image.update(ui.ctx(), &gradient, rect.size(), Dither::Ordered) {
+///     // (re)upload `pixels` as a `size[0]` × `size[1]` texture
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GradientImage {
+    key: Option<(Gradient, [usize; 2], convert::Dither)>,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl GradientImage {
+    /// Regenerates the image if the gradient, the size in points, the dithering or the
+    /// display's `pixels_per_point` (e.g. after moving the window to another monitor)
+    /// changed since the last call. Returns the new size in pixels and the pixels if so.
+    pub fn update(
+        &mut self,
+        ctx: &egui::CtxRef,
+        gradient: &Gradient,
+        size_in_points: egui::Vec2,
+        dither: convert::Dither,
+    ) -> Option<([usize; 2], &[[u8; 4]])> {
+        let pixels = size_in_points * ctx.pixels_per_point();
+        let size = [pixels.x.round().max(1.0) as usize, pixels.y.round().max(1.0) as usize];
+        let key = (gradient.clone(), size, dither);
+        if self.key.as_ref() == Some(&key) {
+            return None;
+        }
+        self.pixels = gradient.to_image(size[0], size[1], dither);
+        self.key = Some(key);
+        Some((size, &self.pixels))
+    }
+
+    /// The pixels from the last [`update`](Self::update), row by row.
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+}
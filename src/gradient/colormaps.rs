@@ -0,0 +1,81 @@
+//! Built-in scientific colormaps, as evenly spaced sRGB samples of the originals.
+
+use super::{Gradient, InterpolationSpace};
+use crate::convert;
+
+/// A common perceptual colormap, see [`colormap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    /// Google's rainbow-like map. Not perceptually uniform in lightness, but much better than jet.
+    Turbo,
+}
+
+const VIRIDIS: &[u32] = &[
+    0x440154, 0x472d7b, 0x3b528b, 0x2c728e, 0x21918c, 0x28ae80, 0x5ec962, 0xaddc30, 0xfde725,
+];
+const MAGMA: &[u32] = &[
+    0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55964, 0xfb8761, 0xfec287, 0xfcfdbf,
+];
+const INFERNO: &[u32] = &[
+    0x000004, 0x1b0c41, 0x4a0c6b, 0x781c6d, 0xa52c60, 0xcf4446, 0xed6925, 0xfb9b06, 0xf7d13d, 0xfcffa4,
+];
+const PLASMA: &[u32] = &[
+    0x0d0887, 0x46039f, 0x7201a8, 0x9c179e, 0xbd3786, 0xd8576b, 0xed7953, 0xfb9f3a, 0xfdca26, 0xf0f921,
+];
+const TURBO: &[u32] = &[
+    0x30123b, 0x4145ab, 0x4675ed, 0x39a2fc, 0x1bcfd4, 0x24eca6, 0x61fc6c, 0xa4fc3b, 0xd1e834, 0xf3c63a,
+    0xfe9b2d, 0xf36315, 0xd93806, 0xb11901, 0x7a0403,
+];
+
+impl Colormap {
+    pub const ALL: [Colormap; 5] = [
+        Colormap::Viridis,
+        Colormap::Magma,
+        Colormap::Inferno,
+        Colormap::Plasma,
+        Colormap::Turbo,
+    ];
+
+    /// The lowercase name, as accepted by [`colormap`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+            Colormap::Inferno => "inferno",
+            Colormap::Plasma => "plasma",
+            Colormap::Turbo => "turbo",
+        }
+    }
+
+    fn samples(self) -> &'static [u32] {
+        match self {
+            Colormap::Viridis => VIRIDIS,
+            Colormap::Magma => MAGMA,
+            Colormap::Inferno => INFERNO,
+            Colormap::Plasma => PLASMA,
+            Colormap::Turbo => TURBO,
+        }
+    }
+
+    /// The colormap as a [`Gradient`] through its samples, interpolated in Oklab.
+    pub fn gradient(self) -> Gradient {
+        let colors: Vec<_> = self
+            .samples()
+            .iter()
+            .map(|&rgb| convert::srgb8_to_oklch([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8, 255]))
+            .collect();
+        Gradient::from_colors(&colors).with_space(InterpolationSpace::Oklab)
+    }
+}
+
+/// The built-in colormap called `name` (case-insensitive), e.g. `colormap("viridis")?.sample(t)`.
+pub fn colormap(name: &str) -> Option<Gradient> {
+    Colormap::ALL
+        .iter()
+        .find(|map| map.name().eq_ignore_ascii_case(name))
+        .map(|map| map.gradient())
+}
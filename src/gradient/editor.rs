@@ -0,0 +1,323 @@
+//! Interactive [`Gradient`] editor widget.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use super::{Colormap, Easing, Gradient, GradientStop, InterpolationSpace, KeyedGradient};
+use crate::sliders::paint_horizontal_gradient;
+use crate::{background_checkers, output_transform, render_quality, Backdrop, ColorEditButton, OklchA, Srgba};
+
+fn to_color32(color: OklchA) -> Color32 {
+    color.convert::<Srgba>().saturate().into_cint().into()
+}
+
+/// Sorts the stops by position, returning where the stop at `selected` ended up. `origins`
+/// (where each stop was before editing started) is reordered along with them.
+fn sort_stops(gradient: &mut Gradient, selected: Option<usize>, origins: &mut Vec<usize>) -> Option<usize> {
+    let mut indexed: Vec<_> = gradient.stops.drain(..).zip(origins.drain(..)).enumerate().collect();
+    indexed.sort_by(|(_, (a, _)), (_, (b, _))| a.t.partial_cmp(&b.t).unwrap_or(std::cmp::Ordering::Equal));
+    let selected = indexed.iter().position(|(i, _)| Some(*i) == selected);
+    let (stops, sorted_origins) = indexed.into_iter().map(|(_, pair)| pair).unzip();
+    gradient.stops = stops;
+    *origins = sorted_origins;
+    selected
+}
+
+/// Editor for the control points of an [`Easing::CubicBezier`], drawn as the curve itself.
+fn cubic_bezier_edit(ui: &mut Ui, handles: &mut [f32; 4]) -> Response {
+    let size = Vec2::splat(ui.spacing().interact_size.y * 4.0);
+    let (rect, mut response) = ui.allocate_exact_size(size, Sense::hover());
+    let visuals = ui.style().noninteractive().clone();
+    let to_screen = |x: f32, y: f32| {
+        pos2(
+            egui::lerp(rect.left()..=rect.right(), x),
+            egui::lerp(rect.bottom()..=rect.top(), y),
+        )
+    };
+
+    let painter = ui.painter();
+    painter.rect_stroke(rect, 0.0, visuals.bg_stroke);
+
+    let easing = Easing::CubicBezier(*handles);
+    let curve = (0..=32)
+        .map(|i| {
+            let x = i as f32 / 32.0;
+            to_screen(x, easing.apply(x))
+        })
+        .collect();
+    painter.add(Shape::line(curve, visuals.fg_stroke));
+
+    let anchors = [to_screen(0.0, 0.0), to_screen(1.0, 1.0)];
+    for k in 0..2 {
+        let center = to_screen(handles[2 * k], handles[2 * k + 1]);
+        let handle_rect = Rect::from_center_size(center, Vec2::splat(12.0));
+        let handle = ui.interact(handle_rect, response.id.with(k), Sense::drag());
+        if let Some(pos) = handle.interact_pointer_pos() {
+            handles[2 * k] = remap_clamp(pos.x, rect.left()..=rect.right(), 0.0..=1.0);
+            handles[2 * k + 1] = remap_clamp(pos.y, rect.bottom()..=rect.top(), 0.0..=1.0);
+            response.mark_changed();
+        }
+
+        let handle_visuals = ui.style().interact(&handle);
+        let painter = ui.painter();
+        painter.line_segment([anchors[k], center], visuals.bg_stroke);
+        painter.circle(center, 4.0, handle_visuals.bg_fill, handle_visuals.fg_stroke);
+        response = response.union(handle);
+    }
+
+    response
+}
+
+fn easing_name(easing: &Easing) -> &'static str {
+    match easing {
+        Easing::Linear => "Linear",
+        Easing::Smoothstep => "Smoothstep",
+        Easing::CubicBezier(_) => "Cubic bezier",
+    }
+}
+
+fn space_name(space: InterpolationSpace) -> &'static str {
+    match space {
+        InterpolationSpace::Oklab => "Oklab",
+        InterpolationSpace::OklchShorter => "Oklch (shorter hue)",
+        InterpolationSpace::OklchLonger => "Oklch (longer hue)",
+        InterpolationSpace::LinearSrgb => "Linear sRGB",
+        InterpolationSpace::EncodedSrgb => "Encoded sRGB",
+    }
+}
+
+/// Shows a gradient bar with draggable stops below it. Clicking a stop selects it for editing
+/// its color and the easing of the segment after it. Double-clicking the bar adds a stop with
+/// the color the gradient has there, and dragging a stop away from the bar removes it.
+pub fn gradient_edit(ui: &mut Ui, gradient: &mut Gradient) -> Response {
+    let mut origins = (0..gradient.stops.len()).collect::<Vec<_>>();
+    gradient_edit_impl(ui, gradient, &mut origins, true)
+}
+
+/// A change to one stop of a [`KeyedGradient`], as reported by [`gradient_edit_keyed`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StopChange<K> {
+    pub key: K,
+    /// The stop before this frame's edit.
+    pub old: GradientStop,
+    pub new: GradientStop,
+}
+
+/// What [`gradient_edit_keyed`] returns.
+pub struct KeyedGradientResponse<K> {
+    pub response: Response,
+    /// One entry per stop whose color, position or easing changed this frame.
+    pub changes: Vec<StopChange<K>>,
+}
+
+/// Like [`gradient_edit`], for a [`KeyedGradient`]. Stops keep their keys while being dragged
+/// past each other. Stops can't be added or removed and the colormap presets are left out,
+/// since the keys are the caller's.
+pub fn gradient_edit_keyed<K: Clone>(ui: &mut Ui, keyed: &mut KeyedGradient<K>) -> KeyedGradientResponse<K> {
+    let mut gradient = keyed.gradient();
+    let mut origins = (0..gradient.stops.len()).collect::<Vec<_>>();
+    let response = gradient_edit_impl(ui, &mut gradient, &mut origins, false);
+
+    let mut changes = Vec::new();
+    let mut stops = Vec::with_capacity(gradient.stops.len());
+    for (stop, origin) in gradient.stops.into_iter().zip(origins) {
+        let (key, old) = &keyed.stops[origin];
+        if *old != stop {
+            changes.push(StopChange {
+                key: key.clone(),
+                old: old.clone(),
+                new: stop.clone(),
+            });
+        }
+        stops.push((key.clone(), stop));
+    }
+    keyed.stops = stops;
+    keyed.space = gradient.space;
+
+    KeyedGradientResponse { response, changes }
+}
+
+/// How far (in points) a stop has to be dragged above or below its handle row to be removed.
+const DETACH_DISTANCE: f32 = 24.0;
+
+/// `origins[i]` is the index stop `i` had in `gradient` when this was called. Stops are only
+/// added, removed or replaced by presets with `change_stops`.
+fn gradient_edit_impl(
+    ui: &mut Ui,
+    gradient: &mut Gradient,
+    origins: &mut Vec<usize>,
+    change_stops: bool,
+) -> Response {
+    let orig_gradient = gradient.clone();
+
+    let width = ui.spacing().slider_width;
+    let bar_height = ui.spacing().interact_size.y * 2.0;
+    let bar_sense = if change_stops { Sense::click() } else { Sense::hover() };
+    let (bar_rect, mut response) = ui.allocate_exact_size(vec2(width, bar_height), bar_sense);
+
+    // From the bar's own id, so that several editors in one `Ui` don't share their selection.
+    let id = response.id.with("gradient_edit");
+    let mut selected = temp_get!(ui, id, usize).filter(|&i| i < gradient.stops.len());
+    let inserted_at = response.interact_pointer_pos().filter(|_| response.double_clicked());
+    background_checkers(ui.painter(), bar_rect, Backdrop::default());
+    let n = render_quality(ui.ctx()).subdivisions_1d;
+    paint_horizontal_gradient(ui.painter(), bar_rect, n, |t| to_color32(gradient.sample(t)));
+    ui.painter()
+        .rect_stroke(bar_rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+    let handle_height = ui.spacing().interact_size.y;
+    let (stops_rect, _) = ui.allocate_exact_size(vec2(width, handle_height), Sense::hover());
+    let mut moved = false;
+    // Only while more than two stops are left, so that it stays a gradient.
+    let can_remove = change_stops && gradient.stops.len() > 2;
+    let detached = |pos: Pos2| (pos.y - stops_rect.center().y).abs() > DETACH_DISTANCE;
+    let mut removed = None;
+    for i in 0..gradient.stops.len() {
+        let stop = &mut gradient.stops[i];
+        let x = egui::lerp(bar_rect.left()..=bar_rect.right(), stop.t);
+        let r = handle_height / 3.0;
+        let handle_rect = Rect::from_min_max(
+            pos2(x - r, stops_rect.top()),
+            pos2(x + r, stops_rect.bottom()),
+        );
+        let handle = ui.interact(handle_rect, id.with(i), Sense::click_and_drag());
+        if handle.clicked() || handle.drag_started() {
+            selected = Some(i);
+        }
+        let pointer = ui.input().pointer.hover_pos();
+        let is_detached = can_remove && (handle.dragged() || handle.drag_released()) && pointer.map_or(false, detached);
+        if handle.drag_released() && is_detached {
+            removed = Some(i);
+        } else if handle.dragged() && !is_detached {
+            if let Some(pos) = handle.interact_pointer_pos() {
+                stop.t = remap_clamp(pos.x, bar_rect.left()..=bar_rect.right(), 0.0..=1.0);
+                moved = true;
+            }
+        }
+
+        let visuals = ui.style().interact(&handle);
+        let stroke = if selected == Some(i) {
+            Stroke::new(2.0, ui.visuals().selection.stroke.color)
+        } else {
+            visuals.fg_stroke
+        };
+        ui.painter().add(Shape::polygon(
+            vec![
+                pos2(x, handle_rect.top()),
+                pos2(x + r, handle_rect.top() + r),
+                pos2(x + r, handle_rect.bottom()),
+                pos2(x - r, handle_rect.bottom()),
+                pos2(x - r, handle_rect.top() + r),
+            ],
+            // Faded while far enough away to be removed on release.
+            output_transform(ui.ctx())
+                .apply(to_color32(stop.color))
+                .to_opaque()
+                .linear_multiply(if is_detached { 0.3 } else { 1.0 }),
+            stroke,
+        ));
+        response = response.union(handle);
+    }
+    if let Some(i) = removed {
+        gradient.stops.remove(i);
+        origins.remove(i);
+        selected = match selected {
+            Some(s) if s == i => None,
+            Some(s) if s > i => Some(s - 1),
+            s => s,
+        };
+    }
+    if moved {
+        selected = sort_stops(gradient, selected, origins);
+    }
+    if let Some(pos) = inserted_at {
+        let t = remap_clamp(pos.x, bar_rect.left()..=bar_rect.right(), 0.0..=1.0);
+        // The color the gradient already has there, so that adding the stop changes nothing.
+        gradient.stops.push(GradientStop::new(t, gradient.sample(t)));
+        origins.push(orig_gradient.stops.len());
+        selected = sort_stops(gradient, Some(gradient.stops.len() - 1), origins);
+    }
+
+    ui.horizontal(|ui| {
+        ComboBox::from_id_source(id.with("space"))
+            .selected_text(space_name(gradient.space))
+            .show_ui(ui, |ui| {
+                for &space in &[
+                    InterpolationSpace::Oklab,
+                    InterpolationSpace::OklchShorter,
+                    InterpolationSpace::OklchLonger,
+                    InterpolationSpace::LinearSrgb,
+                    InterpolationSpace::EncodedSrgb,
+                ] {
+                    ui.selectable_value(&mut gradient.space, space, space_name(space));
+                }
+            });
+        ui.label("Interpolation");
+
+        if !change_stops {
+            return;
+        }
+        ComboBox::from_id_source(id.with("preset"))
+            .selected_text("Presets")
+            .show_ui(ui, |ui| {
+                for &map in &Colormap::ALL {
+                    if ui.selectable_label(false, map.name()).clicked() {
+                        *gradient = map.gradient();
+                        *origins = (0..gradient.stops.len()).collect();
+                        selected = None;
+                    }
+                }
+            });
+    });
+
+    if let Some(i) = selected {
+        let stop = &mut gradient.stops[i];
+        let orig_t = stop.t;
+        ui.horizontal(|ui| {
+            ColorEditButton::new(&mut stop.color).show(ui);
+            ui.add(DragValue::new(&mut stop.t).speed(0.005).clamp_range(0.0..=1.0));
+            ui.label("Position");
+        });
+        if gradient.stops[i].t != orig_t {
+            selected = sort_stops(gradient, selected, origins);
+        }
+    }
+
+    if let Some(stop) = selected.and_then(|i| gradient.stops.get_mut(i)) {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(id.with("easing"))
+                .selected_text(easing_name(&stop.easing))
+                .show_ui(ui, |ui| {
+                    for easing in [
+                        Easing::Linear,
+                        Easing::Smoothstep,
+                        Easing::CubicBezier([0.42, 0.0, 0.58, 1.0]),
+                    ]
+                    .iter()
+                    {
+                        // Compare by kind, so that picking "Cubic bezier" again keeps the handles.
+                        let is_selected = easing_name(&stop.easing) == easing_name(easing);
+                        if ui.selectable_label(is_selected, easing_name(easing)).clicked() && !is_selected {
+                            stop.easing = *easing;
+                        }
+                    }
+                });
+            ui.label("Easing to next stop");
+        });
+        if let Easing::CubicBezier(handles) = &mut stop.easing {
+            cubic_bezier_edit(ui, handles);
+        }
+    }
+
+    if let Some(i) = selected {
+        temp_insert!(ui, id, i);
+    } else {
+        temp_remove!(ui, id, usize);
+    }
+
+    if *gradient != orig_gradient {
+        response.mark_changed();
+    }
+    response
+}
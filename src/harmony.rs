@@ -0,0 +1,104 @@
+//! Color harmonies: colors at fixed hue offsets from a base color.
+
+use crate::{convert, OklchA};
+
+/// A change of lightness and chroma applied to one color of a [`Scheme`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variation {
+    /// Added to the Oklch lightness.
+    pub lightness: f32,
+    /// Multiplies the Oklch chroma.
+    pub chroma_scale: f32,
+}
+
+impl Default for Variation {
+    fn default() -> Self {
+        Self {
+            lightness: 0.0,
+            chroma_scale: 1.0,
+        }
+    }
+}
+
+/// A harmony, e.g. split complementary: hue offsets from the base color, each giving a color.
+/// Apps can offer their own by building one (and, with the `serde` feature, store them with
+/// their settings).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scheme {
+    pub name: String,
+    /// In degrees; `0.0` is the base color itself.
+    pub hue_offsets: Vec<f32>,
+    /// Applied to the colors in turn, repeating when there are more colors than variations,
+    /// e.g. `[Variation::default(), lighter]` makes every other color lighter. Empty to keep
+    /// the base color's lightness and chroma.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variations: Vec<Variation>,
+}
+
+impl Scheme {
+    pub fn new(name: impl Into<String>, hue_offsets: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            name: name.into(),
+            hue_offsets: hue_offsets.into_iter().collect(),
+            variations: Vec::new(),
+        }
+    }
+
+    pub fn with_variations(mut self, variations: impl IntoIterator<Item = Variation>) -> Self {
+        self.variations = variations.into_iter().collect();
+        self
+    }
+
+    pub fn complementary() -> Self {
+        Self::new("Complementary", vec![0.0, 180.0])
+    }
+
+    pub fn split_complementary() -> Self {
+        Self::new("Split complementary", vec![0.0, 150.0, 210.0])
+    }
+
+    pub fn analogous() -> Self {
+        Self::new("Analogous", vec![-30.0, 0.0, 30.0])
+    }
+
+    pub fn triadic() -> Self {
+        Self::new("Triadic", vec![0.0, 120.0, 240.0])
+    }
+
+    pub fn tetradic() -> Self {
+        Self::new("Tetradic", vec![0.0, 90.0, 180.0, 270.0])
+    }
+
+    /// The built-in schemes above.
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::complementary(),
+            Self::split_complementary(),
+            Self::analogous(),
+            Self::triadic(),
+            Self::tetradic(),
+        ]
+    }
+}
+
+/// The colors of `scheme` for `base`, one per hue offset. Alpha is kept.
+pub fn apply_scheme(base: OklchA, scheme: &Scheme) -> Vec<OklchA> {
+    scheme
+        .hue_offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| {
+            let variation = match scheme.variations.len() {
+                0 => Variation::default(),
+                n => scheme.variations[i % n],
+            };
+            let mut color = base;
+            color.col.h = convert::wrap_hue(base.col.h + offset.to_radians());
+            color.col.l = (base.col.l + variation.lightness).clamp(0.0, 1.0);
+            color.col.c = (base.col.c * variation.chroma_scale).max(0.0);
+            color
+        })
+        .collect()
+}
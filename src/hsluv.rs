@@ -0,0 +1,146 @@
+//! HSLuv, a hue/saturation/lightness space built on CIELUV where saturation is
+//! relative to the sRGB gamut boundary at the given hue and lightness.
+//!
+//! Port of the reference implementation at <https://www.hsluv.org>.
+
+use colstodian::*;
+
+use crate::{LinearSrgbA, OklchA, Srgba};
+
+const M: [[f32; 3]; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_6, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+const M_INV: [[f32; 3]; 3] = [
+    [0.412_390_8, 0.357_584_34, 0.180_480_8],
+    [0.212_639, 0.715_168_7, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_2],
+];
+
+const REF_U: f32 = 0.197_830_01;
+const REF_V: f32 = 0.468_319_99;
+const KAPPA: f32 = 903.296_3;
+const EPSILON: f32 = 0.008_856_452;
+
+fn mul(m: [[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// The six lines (slope, intercept) bounding the sRGB gamut in the UV plane at lightness `l`.
+fn bounds(l: f32) -> [(f32, f32); 6] {
+    let sub1 = (l + 16.0).powi(3) / 1_560_896.0;
+    let sub2 = if sub1 > EPSILON { sub1 } else { l / KAPPA };
+
+    let mut lines = [(0.0, 0.0); 6];
+    for (c, [m1, m2, m3]) in M.iter().copied().enumerate() {
+        for t in 0..2 {
+            let t = t as f32;
+            let top1 = (284_517.0 * m1 - 94_839.0 * m3) * sub2;
+            let top2 = (838_422.0 * m3 + 769_860.0 * m2 + 731_718.0 * m1) * l * sub2
+                - 769_860.0 * t * l;
+            let bottom = (632_260.0 * m3 - 126_452.0 * m2) * sub2 + 126_452.0 * t;
+            lines[c * 2 + t as usize] = (top1 / bottom, top2 / bottom);
+        }
+    }
+    lines
+}
+
+fn max_chroma_for_lh(l: f32, h: f32) -> f32 {
+    let (sin, cos) = h.to_radians().sin_cos();
+    bounds(l)
+        .iter()
+        .map(|&(slope, intercept)| intercept / (sin - slope * cos))
+        .filter(|length| *length >= 0.0)
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn y_to_l(y: f32) -> f32 {
+    if y <= EPSILON {
+        y * KAPPA
+    } else {
+        116.0 * y.cbrt() - 16.0
+    }
+}
+
+fn l_to_y(l: f32) -> f32 {
+    if l <= 8.0 {
+        l / KAPPA
+    } else {
+        ((l + 16.0) / 116.0).powi(3)
+    }
+}
+
+fn luv_from_xyz([x, y, z]: [f32; 3]) -> [f32; 3] {
+    let l = y_to_l(y);
+    let divider = x + 15.0 * y + 3.0 * z;
+    if l == 0.0 || divider == 0.0 {
+        return [0.0; 3];
+    }
+    let var_u = 4.0 * x / divider;
+    let var_v = 9.0 * y / divider;
+    [l, 13.0 * l * (var_u - REF_U), 13.0 * l * (var_v - REF_V)]
+}
+
+fn xyz_from_luv([l, u, v]: [f32; 3]) -> [f32; 3] {
+    if l == 0.0 {
+        return [0.0; 3];
+    }
+    let var_u = u / (13.0 * l) + REF_U;
+    let var_v = v / (13.0 * l) + REF_V;
+    let y = l_to_y(l);
+    let x = -(9.0 * y * var_u) / ((var_u - 4.0) * var_v - var_u * var_v);
+    let z = (9.0 * y - 15.0 * var_v * y - var_v * x) / (3.0 * var_v);
+    [x, y, z]
+}
+
+/// Linear sRGB to HSLuv `[hue (degrees), saturation (0..=100), lightness (0..=100)]`.
+pub fn hsluv_from_linear_srgb(rgb: [f32; 3]) -> [f32; 3] {
+    let [l, u, v] = luv_from_xyz(mul(M_INV, rgb));
+    let c = u.hypot(v);
+    let h = if c < 1e-8 {
+        0.0
+    } else {
+        v.atan2(u).to_degrees().rem_euclid(360.0)
+    };
+
+    if l > 99.999_99 {
+        [h, 0.0, 100.0]
+    } else if l < 1e-8 {
+        [h, 0.0, 0.0]
+    } else {
+        [h, c / max_chroma_for_lh(l, h) * 100.0, l]
+    }
+}
+
+/// HSLuv `[hue (degrees), saturation (0..=100), lightness (0..=100)]` to linear sRGB.
+pub fn linear_srgb_from_hsluv([h, s, l]: [f32; 3]) -> [f32; 3] {
+    if l > 99.999_99 {
+        return [1.0; 3];
+    } else if l < 1e-8 {
+        return [0.0; 3];
+    }
+    let c = max_chroma_for_lh(l, h) / 100.0 * s;
+    let (sin, cos) = h.to_radians().sin_cos();
+    mul(M, xyz_from_luv([l, c * cos, c * sin]))
+}
+
+pub(crate) fn oklch_to_hsluv(color: &OklchA) -> [f32; 3] {
+    let lin: LinearSrgbA = color.convert();
+    hsluv_from_linear_srgb([lin.col.r, lin.col.g, lin.col.b])
+}
+
+pub(crate) fn hsluv_to_oklch(hsl: [f32; 3], alpha: f32) -> OklchA {
+    let [r, g, b] = linear_srgb_from_hsluv(hsl);
+    LinearSrgbA::new(r, g, b, alpha).convert()
+}
+
+pub(crate) fn hsluv_to_srgba(hsl: [f32; 3], alpha: f32) -> Srgba {
+    let [r, g, b] = linear_srgb_from_hsluv(hsl);
+    LinearSrgbA::new(r, g, b, alpha).convert()
+}
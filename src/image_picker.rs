@@ -0,0 +1,95 @@
+//! Picking a color by pointing at a pixel of an image.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use crate::{OklchA, Srgba};
+
+/// Pixels on each side of the sampled one that the loupe shows.
+const LOUPE_RADIUS: i32 = 4;
+/// Size of one magnified pixel in the loupe, in points.
+const LOUPE_CELL: f32 = 8.0;
+
+/// The pixel of an `image_size[0]` × `image_size[1]` image under `pos` in `rect`.
+fn pixel_at(rect: Rect, image_size: [usize; 2], pos: Pos2) -> [usize; 2] {
+    let coord = |v: f32, len: usize| ((v * len as f32).floor().max(0.0) as usize).min(len.saturating_sub(1));
+    [
+        coord((pos.x - rect.left()) / rect.width(), image_size[0]),
+        coord((pos.y - rect.top()) / rect.height(), image_size[1]),
+    ]
+}
+
+fn loupe_ui(ui: &mut Ui, image_size: [usize; 2], center: [usize; 2], sample: &impl Fn([usize; 2]) -> Color32) {
+    let side = (2 * LOUPE_RADIUS + 1) as f32 * LOUPE_CELL;
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(side), Sense::hover());
+    let painter = ui.painter();
+    for dy in -LOUPE_RADIUS..=LOUPE_RADIUS {
+        for dx in -LOUPE_RADIUS..=LOUPE_RADIUS {
+            let (x, y) = (center[0] as i32 + dx, center[1] as i32 + dy);
+            let inside = x >= 0 && y >= 0 && (x as usize) < image_size[0] && (y as usize) < image_size[1];
+            let color = if inside {
+                sample([x as usize, y as usize])
+            } else {
+                Color32::TRANSPARENT
+            };
+            let min = rect.min + vec2((dx + LOUPE_RADIUS) as f32, (dy + LOUPE_RADIUS) as f32) * LOUPE_CELL;
+            painter.rect_filled(Rect::from_min_size(min, Vec2::splat(LOUPE_CELL)), 0.0, color);
+        }
+    }
+    let middle = Rect::from_center_size(rect.center(), Vec2::splat(LOUPE_CELL));
+    painter.rect_stroke(middle, 0.0, (1.0, Color32::WHITE));
+    painter.rect_stroke(middle.expand(1.0), 0.0, (1.0, Color32::BLACK));
+    ui.monospace(format!("{}, {}", center[0], center[1]));
+}
+
+/// Shows the image in `texture_id` at `size` and sets `color` to the pixel the user clicks or
+/// drags over, with a loupe of the neighborhood while hovering. The texture is only drawn: the
+/// pixels are read through `sample`, which returns the pixel at `[x, y]` of an
+/// `image_size[0]` × `image_size[1]` image, e.g. `|[x, y]| pixels[y * width + x]`.
+pub fn image_color_picker(
+    ui: &mut Ui,
+    texture_id: TextureId,
+    size: Vec2,
+    image_size: [usize; 2],
+    sample: impl Fn([usize; 2]) -> Color32,
+    color: &mut OklchA,
+) -> Response {
+    let (rect, mut response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+    let mut mesh = Mesh::with_texture(texture_id);
+    mesh.add_rect_with_uv(rect, Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)), Color32::WHITE);
+    ui.painter().add(Shape::mesh(mesh));
+
+    if image_size[0] == 0 || image_size[1] == 0 {
+        return response;
+    }
+
+    if let Some(pos) = response.interact_pointer_pos() {
+        let picked = sample(pixel_at(rect, image_size, pos));
+        let picked: OklchA = Srgba::from(picked.into_cint()).convert();
+        if picked != *color {
+            *color = picked;
+            response.mark_changed();
+        }
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let pixel = pixel_at(rect, image_size, pos);
+        ui.painter().rect_stroke(
+            Rect::from_min_size(
+                pos2(
+                    egui::lerp(rect.left()..=rect.right(), pixel[0] as f32 / image_size[0] as f32),
+                    egui::lerp(rect.top()..=rect.bottom(), pixel[1] as f32 / image_size[1] as f32),
+                ),
+                vec2(rect.width() / image_size[0] as f32, rect.height() / image_size[1] as f32),
+            ),
+            0.0,
+            ui.visuals().selection.stroke,
+        );
+        egui::show_tooltip_at_pointer(ui.ctx(), response.id.with("loupe"), |ui| {
+            loupe_ui(ui, image_size, pixel, &sample);
+        });
+    }
+
+    response
+}
@@ -1,362 +1,539 @@
 //! Color picker widget using Oklab/Oklch color spaces.
+//!
+//! The widgets are in [`widgets`], the picker popup they open in [`picker`] and the sliders it is
+//! built from in [`sliders`]; everything commonly used is re-exported here and in [`prelude`].
+//!
+//! All of it needs the default `colstodian` feature, whose color types the widgets use. Without
+//! it, the crate only has the array-based Oklab/Oklch ⇄ sRGB math of [`convert::plain`].
 
-use std::ops::RangeInclusive;
+#[cfg(feature = "colstodian")]
+use std::sync::Arc;
 
+#[cfg(feature = "colstodian")]
 use egui::*;
+#[cfg(feature = "colstodian")]
 use epaint::*;
+#[cfg(feature = "colstodian")]
 use colstodian::*;
 
-use cint::ColorInterop;
-
+#[cfg(feature = "colstodian")]
 pub type OklchA = ColorAlpha<Oklch, Separate>;
+#[cfg(feature = "colstodian")]
+pub type OklabA = ColorAlpha<Oklab, Separate>;
+#[cfg(feature = "colstodian")]
 #[allow(non_camel_case_types)]
 pub type Srgba = ColorAlpha<EncodedSrgb, Premultiplied>;
+#[cfg(feature = "colstodian")]
+pub(crate) type LinearSrgbA = ColorAlpha<LinearSrgb, Separate>;
+#[cfg(feature = "colstodian")]
+pub(crate) type EncodedSrgbA = ColorAlpha<EncodedSrgb, Separate>;
+
+#[cfg(feature = "colstodian")]
+#[macro_use]
+mod compat;
+
+/// Scopes the enclosing function for the puffin profiler, with the `puffin` feature.
+#[cfg(feature = "colstodian")]
+macro_rules! profile_function {
+    () => {
+        #[cfg(feature = "puffin")]
+        puffin::profile_function!();
+    };
+}
 
-mod cache;
-use cache::Cache;
+/// Scopes the rest of the enclosing block for the puffin profiler, with the `puffin` feature.
+#[cfg(feature = "colstodian")]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        #[cfg(feature = "puffin")]
+        puffin::profile_scope!($name);
+    };
+}
 
-fn contrast_color(color: impl Into<Rgba>) -> Color32 {
-    if color.into().intensity() < 0.5 {
-        Color32::WHITE
-    } else {
-        Color32::BLACK
-    }
+#[cfg(feature = "colstodian")]
+pub mod cache;
+#[cfg(feature = "colstodian")]
+pub mod contrast;
+#[cfg(feature = "colstodian")]
+pub mod convert;
+/// Without the `colstodian` feature, only the dependency-free conversions of [`convert::plain`].
+#[cfg(not(feature = "colstodian"))]
+pub mod convert {
+    pub mod plain;
+}
+#[cfg(feature = "colstodian")]
+pub mod gradient;
+#[cfg(feature = "colstodian")]
+pub mod harmony;
+#[cfg(feature = "colstodian")]
+pub mod palette;
+#[cfg(feature = "colstodian")]
+pub mod picker;
+#[cfg(feature = "colstodian")]
+pub mod sliders;
+#[cfg(feature = "colstodian")]
+pub mod theme;
+#[cfg(feature = "colstodian")]
+pub mod widgets;
+
+#[cfg(feature = "colstodian")]
+pub use contrast::{best_text_color, ContrastMetric, MinContrast};
+#[cfg(feature = "colstodian")]
+pub use convert::{HueUnit, NumberFormat, OutputTransform};
+#[cfg(feature = "colstodian")]
+pub use harmony::{apply_scheme, Scheme};
+#[cfg(feature = "colstodian")]
+pub use gradient::{
+    colormap, gradient_edit, gradient_edit_keyed, Colormap, Easing, Gradient, GradientImage, GradientStop,
+    InterpolationSpace, KeyedGradient, KeyedGradientResponse, StopChange,
+};
+#[cfg(feature = "colstodian")]
+pub use cache::{clear_color_picker_caches, get_cached_oklch, set_cached_oklch};
+#[cfg(feature = "colstodian")]
+pub use picker::{
+    close_color_picker, open_color_picker, ButtonTooltip, ColorEditButton, DoubleClickAction, PickerControl, PickerMode,
+    PickerResponse, SharedPicker,
+};
+#[cfg(feature = "colstodian")]
+pub use sliders::{MarkerShape, MarkerStyle};
+#[cfg(all(feature = "colstodian", feature = "bevy"))]
+pub use widgets::color_edit_button_bevy;
+#[cfg(feature = "colstodian")]
+pub use widgets::{
+    color_edit_button, color_edit_button_multi, color_edit_button_oklab, color_edit_button_oklch,
+    color_edit_button_rgba, color_edit_widget, gradient_edit_widget, stroke_edit, stroke_fill_edit,
+};
+
+#[cfg(feature = "colstodian")]
+use picker::PickerOptions;
+#[cfg(feature = "colstodian")]
+use sliders::color_slider_1d;
+
+/// The widgets and types most apps need, for `use egui_color_picker_oklab::prelude::*;`.
+#[cfg(feature = "colstodian")]
+pub mod prelude {
+    pub use crate::{
+        color_edit_button, color_edit_button_oklch, color_edit_button_rgba, gradient_edit, palette_grid, stroke_edit,
+        ColorEditButton, Gradient, OklabA, OklchA, Palette, PickerMode, PickerResponse, SharedPicker, Srgba,
+    };
 }
 
-/// Number of vertices per dimension in the color sliders.
-/// We need at least 6 for hues, and more for smooth 2D areas.
-/// Should always be a multiple of 6 to hit the peak hues in HSV/HSL (every 60°).
-const N: u32 = 6 * 6;
+/// Serializes an [`OklchA`] as `[l, c, h, alpha]`, for `#[serde(with = "...")]`.
+#[cfg(all(feature = "colstodian", feature = "serde"))]
+pub(crate) mod serde_oklch {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-fn background_checkers(painter: &Painter, rect: Rect) {
-    let rect = rect.shrink(0.5); // Small hack to avoid the checkers from peeking through the sides
+    use crate::OklchA;
 
-    let mut top_color = Color32::from_gray(128);
-    let mut bottom_color = Color32::from_gray(32);
-    let checker_size = Vec2::splat(rect.height() / 2.0);
-    let n = (rect.width() / checker_size.x).round() as u32;
+    pub fn serialize<S: Serializer>(color: &OklchA, serializer: S) -> Result<S::Ok, S::Error> {
+        [color.col.l, color.col.c, color.col.h, color.alpha].serialize(serializer)
+    }
 
-    let mut mesh = Mesh::default();
-    for i in 0..n {
-        let x = egui::lerp(rect.left()..=rect.right(), i as f32 / (n as f32));
-        mesh.add_colored_rect(
-            Rect::from_min_size(pos2(x, rect.top()), checker_size),
-            top_color,
-        );
-        mesh.add_colored_rect(
-            Rect::from_min_size(pos2(x, rect.center().y), checker_size),
-            bottom_color,
-        );
-        std::mem::swap(&mut top_color, &mut bottom_color);
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OklchA, D::Error> {
+        let [l, c, h, alpha] = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(OklchA::new(l, c, h, alpha))
     }
-    painter.add(Shape::mesh(mesh));
+}
+#[cfg(feature = "colstodian")]
+pub use palette::{
+    auto_name, delta_e_heatmap, delta_e_matrix, extract_palette, generate_categorical, palette_grid,
+    CategoricalConstraints, DeltaEHeatmap, Palette, PaletteEntry, PaletteGrid, SortKey,
+};
+
+#[cfg(feature = "colstodian")]
+mod animate;
+#[cfg(feature = "colstodian")]
+pub use animate::animate_color;
+
+#[cfg(feature = "colstodian")]
+mod cielab;
+#[cfg(feature = "colstodian")]
+pub use cielab::WhitePoint;
+
+#[cfg(feature = "colstodian")]
+mod hsluv;
+
+#[cfg(feature = "colstodian")]
+mod image_picker;
+#[cfg(feature = "colstodian")]
+pub use image_picker::image_color_picker;
+
+#[cfg(feature = "colstodian")]
+mod cmyk;
+
+#[cfg(feature = "colstodian")]
+mod composite;
+#[cfg(feature = "colstodian")]
+pub use composite::{composite_over, uncomposite, CompositeSpace};
+
+#[cfg(feature = "colstodian")]
+mod model;
+#[cfg(feature = "colstodian")]
+pub use model::{ColorConstraint, ColorValidator, GamutPolicy, PickerModel};
+
+#[cfg(feature = "colstodian")]
+mod names;
+
+#[cfg(feature = "colstodian")]
+mod snap;
+#[cfg(feature = "colstodian")]
+pub use snap::Snap;
+
+#[cfg(feature = "colstodian")]
+mod temperature;
+
+#[cfg(feature = "colstodian")]
+mod token;
+#[cfg(feature = "colstodian")]
+pub use token::{token_color_edit, TokenSource};
+
+/// How finely the color sliders are tessellated, traded off against vertex count. Set it for a
+/// whole app with [`set_render_quality`], or per button with
+/// [`ColorEditButton::render_quality`].
+///
+/// Subdivisions should be multiples of 6 to hit the peak hues in HSV/HSL (every 60°).
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderQuality {
+    /// Segments of the 1D sliders and gradient bars.
+    pub subdivisions_1d: u32,
+    /// Cells per side of the 2D slider areas.
+    pub subdivisions_2d: u32,
+    /// Paint the 2D areas from textures instead of vertex-colored meshes. Currently ignored: in
+    /// this egui version textures can only be allocated through the integration's
+    /// `epi::TextureAllocator`, which widgets can't reach, so meshes are always used.
+    pub use_textures: bool,
 }
 
-fn show_color(ui: &mut Ui, color: Color32, desired_size: Vec2) -> Response {
-    let (rect, response) = ui.allocate_at_least(desired_size, Sense::hover());
-    background_checkers(ui.painter(), rect);
-    if true {
-        let left = Rect::from_min_max(rect.left_top(), rect.center_bottom());
-        let right = Rect::from_min_max(rect.center_top(), rect.right_bottom());
-        ui.painter().rect_filled(left, 0.0, color);
-        ui.painter().rect_filled(right, 0.0, color.to_opaque());
-    } else {
-        ui.painter().add(Shape::Rect {
-            rect,
-            corner_radius: 2.0,
-            fill: color.into(),
-            stroke: Stroke::new(3.0, color.to_opaque()),
-        });
+#[cfg(feature = "colstodian")]
+impl RenderQuality {
+    /// For low-power and embedded targets; hue sliders still hit the peak hues.
+    pub const LOW: Self = Self {
+        subdivisions_1d: 12,
+        subdivisions_2d: 12,
+        use_textures: false,
+    };
+    pub const MEDIUM: Self = Self {
+        subdivisions_1d: 36,
+        subdivisions_2d: 36,
+        use_textures: false,
+    };
+    /// Smoother 2D areas on large or high-DPI displays.
+    pub const HIGH: Self = Self {
+        subdivisions_1d: 72,
+        subdivisions_2d: 72,
+        use_textures: false,
+    };
+}
+
+#[cfg(feature = "colstodian")]
+impl Default for RenderQuality {
+    fn default() -> Self {
+        RenderQuality::MEDIUM
     }
-    response
 }
 
-fn color_button(ui: &mut Ui, color: Color32) -> Response {
-    let size = ui.spacing().interact_size;
-    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
-    response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
-    let visuals = ui.style().interact(&response);
-    let rect = rect.expand(visuals.expansion);
+/// Sets the [`RenderQuality`] of all pickers in `ctx` that don't set their own. Defaults to
+/// [`RenderQuality::MEDIUM`].
+#[cfg(feature = "colstodian")]
+pub fn set_render_quality(ctx: &CtxRef, quality: RenderQuality) {
+    *ctx.memory().data_temp.get_mut_or_default::<RenderQuality>() = quality;
+}
 
-    background_checkers(ui.painter(), rect);
+#[cfg(feature = "colstodian")]
+fn render_quality(ctx: &CtxRef) -> RenderQuality {
+    *ctx.memory().data_temp.get_or_default::<RenderQuality>()
+}
 
-    let left_half = Rect::from_min_max(rect.left_top(), rect.center_bottom());
-    let right_half = Rect::from_min_max(rect.center_top(), rect.right_bottom());
-    ui.painter().rect_filled(left_half, 0.0, color);
-    ui.painter().rect_filled(right_half, 0.0, color.to_opaque());
+/// Sets the [`OutputTransform`] applied to the color previews (swatches, slider fills and
+/// palettes) of all widgets in `ctx`, e.g. [`OutputTransform::DisplayP3`] for a compositor that
+/// expects Display P3. Defaults to [`OutputTransform::Srgb`].
+#[cfg(feature = "colstodian")]
+pub fn set_output_transform(ctx: &CtxRef, transform: OutputTransform) {
+    *ctx.memory().data_temp.get_mut_or_default::<OutputTransform>() = transform;
+}
 
-    let corner_radius = visuals.corner_radius.at_most(2.0);
-    ui.painter()
-        .rect_stroke(rect, corner_radius, (2.0, visuals.bg_fill)); // fill is intentional!
+#[cfg(feature = "colstodian")]
+fn output_transform(ctx: &CtxRef) -> OutputTransform {
+    *ctx.memory().data_temp.get_or_default::<OutputTransform>()
+}
 
-    response
+#[cfg(feature = "colstodian")]
+type FormatFn = Arc<dyn Fn(OklchA) -> String + Send + Sync>;
+
+/// The clipboard formats added with [`register_custom_format`].
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Default)]
+struct CustomFormats(Vec<(String, FormatFn)>);
+
+/// Adds a button named `name` to the "Copy as code" section of all pickers in `ctx`, copying
+/// `format` of the picked color, e.g. for an engine's own color syntax:
+///
+/// ```ignore
+/// register_custom_format(ctx, "Engine", |color| {
+///     let [r, g, b, a] = convert::oklch_to_srgb8(color).map(|v| v as f32 / 255.0);
+///     format!("Color({:.3}, {:.3}, {:.3}, {:.3})", r, g, b, a)
+/// });
+/// ```
+///
+/// Registering a name again replaces its format, so this can be called every frame.
+#[cfg(feature = "colstodian")]
+pub fn register_custom_format(
+    ctx: &CtxRef,
+    name: impl Into<String>,
+    format: impl Fn(OklchA) -> String + Send + Sync + 'static,
+) {
+    let name = name.into();
+    let mut memory = ctx.memory();
+    let formats = &mut memory.data_temp.get_mut_or_default::<CustomFormats>().0;
+    let format: FormatFn = Arc::new(format);
+    match formats.iter_mut().find(|(existing, _)| *existing == name) {
+        Some(entry) => entry.1 = format,
+        None => formats.push((name, format)),
+    }
 }
 
-fn color_slider_1d(ui: &mut Ui, value: &mut f32, range: RangeInclusive<f32>, color_at: impl Fn(f32) -> Color32) -> Response {
-    #![allow(clippy::identity_op)]
+#[cfg(feature = "colstodian")]
+fn custom_formats(ctx: &CtxRef) -> CustomFormats {
+    ctx.memory().data_temp.get_or_default::<CustomFormats>().clone()
+}
 
-    let desired_size = vec2(
-        ui.spacing().slider_width,
-        ui.spacing().interact_size.y * 2.0,
-    );
-    let (rect, response) = ui.allocate_at_least(desired_size, Sense::click_and_drag());
+/// What is drawn behind transparent colors.
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Backdrop {
+    /// A checkerboard. `size` is the side of a checker in points; `None` fits two checkers
+    /// into the height of whatever is drawn.
+    Checkers {
+        light: Color32,
+        dark: Color32,
+        size: Option<f32>,
+    },
+    /// One color, e.g. the canvas color an app will actually composite the color over.
+    Solid(Color32),
+}
 
-    if let Some(mpos) = response.interact_pointer_pos() {
-        *value = remap_clamp(mpos.x, rect.left()..=rect.right(), range.clone());
+#[cfg(feature = "colstodian")]
+impl Default for Backdrop {
+    fn default() -> Self {
+        Backdrop::Checkers {
+            light: Color32::from_gray(128),
+            dark: Color32::from_gray(32),
+            size: None,
+        }
     }
+}
 
-    let visuals = ui.style().interact(&response);
+#[cfg(feature = "colstodian")]
+fn background_checkers(painter: &Painter, rect: Rect, backdrop: Backdrop) {
+    let rect = rect.shrink(0.5); // Small hack to avoid the checkers from peeking through the sides
 
-    background_checkers(ui.painter(), rect); // for alpha:
-
-    {
-        // fill color:
-        let mut mesh = Mesh::default();
-        for i in 0..=N {
-            let t = i as f32 / (N as f32);
-            let color = color_at(egui::lerp(range.clone(), t));
-            let x = egui::lerp(rect.left()..=rect.right(), t);
-            mesh.colored_vertex(pos2(x, rect.top()), color);
-            mesh.colored_vertex(pos2(x, rect.bottom()), color);
-            if i < N {
-                mesh.add_triangle(2 * i + 0, 2 * i + 1, 2 * i + 2);
-                mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
-            }
+    let (light, dark, size) = match backdrop {
+        Backdrop::Checkers { light, dark, size } => (light, dark, size),
+        Backdrop::Solid(color) => {
+            painter.rect_filled(rect, 0.0, color);
+            return;
         }
-        ui.painter().add(Shape::mesh(mesh));
-    }
+    };
+    let size = size.unwrap_or(rect.height() / 2.0).max(1.0);
+    let nx = (rect.width() / size).round().max(1.0) as u32;
+    let ny = (rect.height() / size).round().max(1.0) as u32;
+    let checker_size = vec2(rect.width() / nx as f32, rect.height() / ny as f32);
 
-    ui.painter().rect_stroke(rect, 0.0, visuals.bg_stroke); // outline
-
-    {
-        // Show where the slider is at:
-        let x = egui::lerp(rect.left()..=rect.right(), remap_clamp(*value, range.clone(), 0.0..=1.0));
-        let r = rect.height() / 4.0;
-        let picked_color = color_at(*value);
-        ui.painter().add(Shape::polygon(
-            vec![
-                pos2(x - r, rect.bottom()),
-                pos2(x + r, rect.bottom()),
-                pos2(x, rect.center().y),
-            ],
-            picked_color,
-            Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
-        ));
+    let mut mesh = Mesh::default();
+    mesh.reserve_vertices(4 * (nx * ny) as usize);
+    mesh.reserve_triangles(2 * (nx * ny) as usize);
+    for y in 0..ny {
+        for x in 0..nx {
+            let min = rect.min + vec2(x as f32, y as f32) * checker_size;
+            let color = if (x + y) % 2 == 0 { light } else { dark };
+            mesh.add_colored_rect(Rect::from_min_size(min, checker_size), color);
+        }
     }
-
-    response
+    painter.add(Shape::mesh(mesh));
 }
 
-fn color_slider_2d(
-    ui: &mut Ui,
-    x_value: &mut f32,
-    x_range: RangeInclusive<f32>,
-    y_value: &mut f32,
-    y_range: RangeInclusive<f32>,
-    color_at: impl Fn(f32, f32) -> Color32,
-) -> Response {
-    let desired_size = Vec2::splat(ui.spacing().slider_width);
-    let (rect, response) = ui.allocate_at_least(desired_size, Sense::click_and_drag());
+/// How swatches of a color with alpha are drawn.
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlphaDisplay {
+    /// The left half as the color over checkers, the right half fully opaque.
+    SplitHalves,
+    /// One swatch of the color composited over checkers.
+    BlendedOverCheckers,
+    /// Just the opaque color, ignoring alpha.
+    OpaqueOnly,
+}
 
-    if let Some(mpos) = response.interact_pointer_pos() {
-        *x_value = remap_clamp(mpos.x, rect.left()..=rect.right(), x_range.clone());
-        *y_value = remap_clamp(mpos.y, rect.bottom()..=rect.top(), y_range.clone());
+#[cfg(feature = "colstodian")]
+impl Default for AlphaDisplay {
+    fn default() -> Self {
+        AlphaDisplay::SplitHalves
     }
+}
 
-    let visuals = ui.style().interact(&response);
-    let mut mesh = Mesh::default();
+/// How the alpha sliders show transparency; the user can switch with the toggle beside them.
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlphaPreview {
+    /// The color at each alpha as stored premultiplied, composited over the checkers.
+    Premultiplied,
+    /// The straight (unassociated) color channels at each alpha, blended over the checkers in
+    /// encoded sRGB, as image editors working with straight alpha show it.
+    Straight,
+}
 
-    for xi in 0..=N {
-        for yi in 0..=N {
-            let xt = xi as f32 / (N as f32);
-            let yt = yi as f32 / (N as f32);
-            let color = color_at(egui::lerp(x_range.clone(), xt), egui::lerp(y_range.clone(), yt));
-            let x = egui::lerp(rect.left()..=rect.right(), xt);
-            let y = egui::lerp(rect.bottom()..=rect.top(), yt);
-            mesh.colored_vertex(pos2(x, y), color);
-
-            if xi < N && yi < N {
-                let x_offset = 1;
-                let y_offset = N + 1;
-                let tl = yi * y_offset + xi;
-                mesh.add_triangle(tl, tl + x_offset, tl + y_offset);
-                mesh.add_triangle(tl + x_offset, tl + y_offset, tl + y_offset + x_offset);
-            }
-        }
+#[cfg(feature = "colstodian")]
+impl Default for AlphaPreview {
+    fn default() -> Self {
+        AlphaPreview::Premultiplied
     }
-    ui.painter().add(Shape::mesh(mesh)); // fill
-
-    ui.painter().rect_stroke(rect, 0.0, visuals.bg_stroke); // outline
-
-    // Show where the slider is at:
-    let x = egui::lerp(rect.left()..=rect.right(), remap_clamp(*x_value, x_range.clone(), 0.0..=1.0));
-    let y = egui::lerp(rect.bottom()..=rect.top(), remap_clamp(*y_value, y_range.clone(), 0.0..=1.0));
-    let picked_color = color_at(*x_value, *y_value);
-    ui.painter().add(Shape::Circle {
-        center: pos2(x, y),
-        radius: rect.width() / 12.0,
-        fill: picked_color,
-        stroke: Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
-    });
+}
 
-    response
+/// Where the user's choice of [`AlphaPreview`] is kept, overriding
+/// [`ColorEditButton::alpha_preview`] for all pickers.
+#[cfg(feature = "colstodian")]
+fn alpha_preview_id() -> Id {
+    Id::new("color_picker_alpha_preview")
+}
+
+/// The alpha slider of a picker, where `color_at(a)` is the picked color with alpha `a`.
+#[cfg(feature = "colstodian")]
+fn alpha_slider(
+    ui: &mut Ui,
+    options: &PickerOptions,
+    alpha: &mut f32,
+    color_at: impl Fn(f32) -> Color32,
+) -> Response {
+    let preview = temp_get!(ui, alpha_preview_id(), AlphaPreview).unwrap_or(options.alpha_preview);
+    color_slider_1d(ui, options, alpha, 0.0..=1.0, |a| match preview {
+        AlphaPreview::Premultiplied => color_at(a),
+        AlphaPreview::Straight => {
+            let [r, g, b, _] = color_at(1.0).to_array();
+            Color32::from_rgba_unmultiplied(r, g, b, (a.clamp(0.0, 1.0) * 255.0).round() as u8)
+        }
+    })
 }
 
-fn color_text_ui(ui: &mut Ui, color: Srgba) {
+/// The "Alpha" label next to [`alpha_slider`], with a toggle for the [`AlphaPreview`].
+#[cfg(feature = "colstodian")]
+fn alpha_label_ui(ui: &mut Ui, options: &PickerOptions) {
     ui.horizontal(|ui| {
-        let [r, g, b, a] = color.to_u8();
-        ui.label(format!(
-            "Encoded sRGB + Alpha (premultiplied): ({}, {}, {}, {})",
-            r, g, b, a
-        ));
-
-        if ui.button("📋").on_hover_text("Click to copy").clicked() {
-            ui.output().copied_text = format!("{}, {}, {}, {}", r, g, b, a);
+        ui.label("Alpha");
+        let id = alpha_preview_id();
+        let preview = temp_get!(ui, id, AlphaPreview).unwrap_or(options.alpha_preview);
+        let (text, next) = match preview {
+            AlphaPreview::Premultiplied => ("premul", AlphaPreview::Straight),
+            AlphaPreview::Straight => ("straight", AlphaPreview::Premultiplied),
+        };
+        if ui
+            .small_button(text)
+            .on_hover_text("How the slider previews alpha; click to switch")
+            .clicked()
+        {
+            temp_insert!(ui, id, next);
         }
     });
 }
 
-fn color_picker_oklch_2d(ui: &mut Ui, color: &mut OklchA, col_srgba: Srgba) -> bool {
-    let orig_col = *color;
-
-    color_text_ui(ui, col_srgba);
-
-    let grid_id = "oklab_color_picker";
-
-    crate::Grid::new(grid_id).show(ui, |ui| {
-        let current_color_size = vec2(
-            ui.spacing().slider_width,
-            ui.spacing().interact_size.y * 2.0,
-        );
-
-        let mut opaque = *color;
-        opaque.alpha = 1.0;
+/// Which controls the pickers show, see [`ColorEditButton::controls`]. Hue, chroma and the 2D
+/// area only exist in some modes; the other flags apply to all modes that have the control.
+/// HSLuv's saturation counts as chroma, and the temperature and tint of
+/// [`PickerMode::Temperature`] as hue and chroma.
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Controls {
+    pub hue: bool,
+    pub chroma: bool,
+    pub lightness: bool,
+    pub alpha: bool,
+    /// The 2D slider area.
+    pub area: bool,
+    /// The swatch of the selected color.
+    pub preview: bool,
+    /// The text readouts and copy buttons.
+    pub text: bool,
+}
 
-        color_slider_1d(ui, &mut color.alpha, 0.0..=1.0, |a| {
-            let mut col = opaque;
-            col.alpha = a;
-            col.convert_to::<Srgba>().saturate().into_cint().into()
-        });
-        ui.label("Alpha");
-        ui.end_row();
-
-        show_color(ui, color.convert_to::<Srgba>().saturate().into_cint().into(), current_color_size);
-        ui.label("Selected color");
-        ui.end_row();
-
-        ui.separator(); // TODO: fix ever-expansion
-        ui.end_row();
-
-        use core::f32::consts::PI;
-        color_slider_1d(ui, &mut color.col.h, -PI..=PI, |h| {
-            let mut col = opaque;
-            col.col.h = h;
-            col.convert_to::<Srgba>().saturate().into_cint().into()
-        });
-        ui.label("Hue");
-        ui.end_row();
-
-        color_slider_1d(ui, &mut color.col.c,0.0..=0.5, |c| {
-            let mut col = opaque;
-            col.col.c = c;
-            col.convert_to::<Srgba>().saturate().into_cint().into()
-        });
-        ui.label("Chroma");
-        ui.end_row();
-
-        color_slider_1d(ui, &mut color.col.l, 0.0..=1.0, |l| {
-            let mut col = opaque;
-            col.col.l = l;
-            col.convert_to::<Srgba>().saturate().into_cint().into()
-        });
-        ui.label("Lightness");
-        ui.end_row();
-
-        let col = &mut color.col;
-        color_slider_2d(ui, &mut col.c, 0.0..=0.5, &mut col.l, 0.0..=1.0, |c, l| {
-            let mut col = opaque;
-            col.col.c = c;
-            col.col.l = l;
-            col.convert_to::<Srgba>().saturate().into_cint().into()
-        });
-        ui.label("Lightness / Chroma");
-        ui.end_row();
-    });
+#[cfg(feature = "colstodian")]
+impl Controls {
+    pub const ALL: Self = Self {
+        hue: true,
+        chroma: true,
+        lightness: true,
+        alpha: true,
+        area: true,
+        preview: true,
+        text: true,
+    };
+    /// Start from this to show only some controls, e.g. `Controls { lightness: true, ..Controls::NONE }`
+    /// for a grayscale tool.
+    pub const NONE: Self = Self {
+        hue: false,
+        chroma: false,
+        lightness: false,
+        alpha: false,
+        area: false,
+        preview: false,
+        text: false,
+    };
+}
 
-    if *color == orig_col {
-        false
-    } else {
-        true
+#[cfg(feature = "colstodian")]
+impl Default for Controls {
+    fn default() -> Self {
+        Controls::ALL
     }
 }
 
-pub fn color_edit_button_oklch(ui: &mut Ui, color: &mut OklchA) -> Response {
-    let col_srgba: Srgba = color.convert();
-    let popup_id = ui.make_persistent_id("popup");
-    let mut button_response = color_button(ui, col_srgba.into_cint().into()).on_hover_text("Click to edit color");
+/// How color swatches are drawn, see [`ColorEditButton::alpha_display`] and
+/// [`ColorEditButton::backdrop`].
+#[cfg(feature = "colstodian")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct SwatchStyle {
+    pub alpha: AlphaDisplay,
+    pub backdrop: Backdrop,
+}
 
-    if button_response.clicked() {
-        ui.memory().toggle_popup(popup_id);
-    }
-    // TODO: make it easier to show a temporary popup that closes when you click outside it
-    if ui.memory().is_popup_open(popup_id) {
-        let area_response = Area::new(popup_id)
-            .order(Order::Foreground)
-            .default_pos(button_response.rect.max)
-            .show(ui.ctx(), |ui| {
-                ui.spacing_mut().slider_width = 256.0;
-                Frame::popup(ui.style()).show(ui, |ui| {
-                    if color_picker_oklch_2d(ui, color, col_srgba) {
-                        button_response.mark_changed();
-                    }
-                });
-            });
-
-        if !button_response.clicked()
-            && (ui.input().key_pressed(Key::Escape) || area_response.clicked_elsewhere())
-        {
-            ui.memory().close_popup();
+#[cfg(feature = "colstodian")]
+fn paint_swatch(painter: &Painter, rect: Rect, color: Color32, style: SwatchStyle) {
+    let color = output_transform(painter.ctx()).apply(color);
+    match style.alpha {
+        AlphaDisplay::SplitHalves => {
+            background_checkers(painter, rect, style.backdrop);
+            let left = Rect::from_min_max(rect.left_top(), rect.center_bottom());
+            let right = Rect::from_min_max(rect.center_top(), rect.right_bottom());
+            painter.rect_filled(left, 0.0, color);
+            painter.rect_filled(right, 0.0, color.to_opaque());
         }
+        AlphaDisplay::BlendedOverCheckers => {
+            background_checkers(painter, rect, style.backdrop);
+            painter.rect_filled(rect, 0.0, color);
+        }
+        AlphaDisplay::OpaqueOnly => painter.rect_filled(rect, 0.0, color.to_opaque()),
     }
-
-    button_response
 }
 
-fn color_edit_button_inner(ui: &mut Ui, color: &mut Srgba) -> Response {
-    // To ensure we keep hue slider when `color` is gray we store the
-    // full Oklch color in a cache:
-
-    let mut oklch = ui
-        .ctx()
-        .memory()
-        .data_temp
-        .get_or_default::<Cache<[u8; 4], OklchA>>()
-        .get(&color.to_u8())
-        .cloned()
-        .unwrap_or_else(|| color.convert());
-
-    let response = color_edit_button_oklch(ui, &mut oklch);
-
-    *color = oklch.convert();
-
-    ui.ctx()
-        .memory()
-        .data_temp
-        .get_mut_or_default::<Cache<[u8; 4], OklchA>>()
-        .set(color.to_u8(), oklch);
-
+#[cfg(feature = "colstodian")]
+fn show_color(ui: &mut Ui, color: Color32, desired_size: Vec2, style: SwatchStyle) -> Response {
+    let (rect, response) = ui.allocate_at_least(desired_size, Sense::hover());
+    paint_swatch(ui.painter(), rect, color, style);
     response
 }
 
-/// Shows a button with the given color.
-/// If the user clicks the button, a full color picker is shown.
-pub fn color_edit_button(ui: &mut Ui, color: &mut Color32) -> Response {
-    let mut col = Srgba::from(color.into_cint());
+#[cfg(feature = "colstodian")]
+fn color_button(ui: &mut Ui, color: Color32, style: SwatchStyle) -> Response {
+    let size = ui.spacing().interact_size;
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
+    let visuals = ui.style().interact(&response);
+    let rect = rect.expand(visuals.expansion);
 
-    let res = color_edit_button_inner(ui, &mut col);
+    paint_swatch(ui.painter(), rect, color, style);
 
-    *color = Color32::from_cint(col.to_u8().into());
+    let corner_radius = visuals.corner_radius.at_most(2.0);
+    ui.painter()
+        .rect_stroke(rect, corner_radius, (2.0, visuals.bg_fill)); // fill is intentional!
 
-    res
+    response
 }
-
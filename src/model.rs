@@ -0,0 +1,274 @@
+//! The picker's state handling without any UI, for custom frontends.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{convert, OklchA, Snap};
+
+/// What happens to edited colors outside the sRGB gamut.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamutPolicy {
+    /// Keep them; they are clipped only when displayed.
+    Allow,
+    /// Reduce their chroma until they fit, keeping lightness and hue.
+    ReduceChroma,
+}
+
+impl Default for GamutPolicy {
+    fn default() -> Self {
+        GamutPolicy::Allow
+    }
+}
+
+/// Restricts editing to the colors a predicate allows, e.g. brand guidelines:
+///
+/// ```ignore
+/// let brand = ColorConstraint::new(|c| (0.3..=0.8).contains(&c.col.l) && c.col.c <= 0.2);
+/// ```
+///
+/// An edit leaving the allowed region stops at its edge instead, and the pickers' 2D areas dim
+/// what isn't allowed.
+#[derive(Clone)]
+pub struct ColorConstraint(Arc<dyn Fn(OklchA) -> bool + Send + Sync>);
+
+impl ColorConstraint {
+    pub fn new(allows: impl Fn(OklchA) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(allows))
+    }
+
+    pub fn allows(&self, color: OklchA) -> bool {
+        (self.0)(color)
+    }
+
+    /// The edit from `from` to `to` limited to the allowed colors: `to` if allowed, otherwise
+    /// the last allowed color on the way there. Componentwise in Oklch, as sliders move.
+    /// If `from` isn't allowed either, there is nothing to go back to and `to` is kept.
+    pub fn limit(&self, from: OklchA, to: OklchA) -> OklchA {
+        if self.allows(to) || !self.allows(from) {
+            return to;
+        }
+        let at = |t: f32| {
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+            OklchA::new(
+                lerp(from.col.l, to.col.l),
+                lerp(from.col.c, to.col.c),
+                lerp(from.col.h, to.col.h),
+                lerp(from.alpha, to.alpha),
+            )
+        };
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..16 {
+            let mid = (lo + hi) / 2.0;
+            if self.allows(at(mid)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        at(lo)
+    }
+}
+
+impl fmt::Debug for ColorConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ColorConstraint")
+    }
+}
+
+/// Checks picked colors against an app's rule, explaining why a color fails:
+///
+/// ```ignore
+/// let readable = ColorValidator::new(|c| {
+///     let ratio = contrast::wcag_contrast_ratio(c.convert::<Srgba>().into_cint().into(), Color32::WHITE);
+///     if ratio >= 4.5 {
+///         Ok(())
+///     } else {
+///         Err(format!("Contrast with white is {:.1}:1, needs 4.5:1", ratio))
+///     }
+/// });
+/// ```
+///
+/// Unlike a [`ColorConstraint`], any color can still be picked; the picker shows the message
+/// while the color fails, and can refuse to commit it, see
+/// [`ColorEditButton::block_invalid`](crate::ColorEditButton::block_invalid).
+#[derive(Clone)]
+pub struct ColorValidator(Arc<dyn Fn(&OklchA) -> Result<(), String> + Send + Sync>);
+
+impl ColorValidator {
+    pub fn new(validate: impl Fn(&OklchA) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(validate))
+    }
+
+    /// `Err` with the message to show if `color` fails the rule.
+    pub fn validate(&self, color: &OklchA) -> Result<(), String> {
+        (self.0)(color)
+    }
+}
+
+impl fmt::Debug for ColorValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ColorValidator")
+    }
+}
+
+/// Applies `gamut`, `snap` and then `constraint` to an edit from `from` to `color`, as the picker
+/// does.
+pub(crate) fn constrain(
+    from: OklchA,
+    mut color: OklchA,
+    gamut: GamutPolicy,
+    snap: &Snap,
+    constraint: Option<&ColorConstraint>,
+) -> OklchA {
+    if gamut == GamutPolicy::ReduceChroma {
+        color.col.c = color.col.c.min(convert::max_chroma_for(color.col.l, color.col.h));
+    }
+    let color = snap.apply(color);
+    match constraint {
+        Some(constraint) => constraint.limit(from, color),
+        None => color,
+    }
+}
+
+/// Adds `color` as the newest entry of `history` (unless it already is), keeping at most `len`.
+pub(crate) fn push_history(history: &mut Vec<OklchA>, color: OklchA, len: usize) {
+    if history.last() != Some(&color) {
+        history.push(color);
+    }
+    let excess = history.len().saturating_sub(len);
+    history.drain(..excess);
+}
+
+/// The state of a color being edited: the current color, the one editing started from (which
+/// [`cancel`](Self::cancel) goes back to), and the history of committed colors. Drive it from
+/// custom widgets, e.g. a radial picker, to get the same constraints and bookkeeping as
+/// [`ColorEditButton`](crate::ColorEditButton).
+///
+/// ```ignore
+/// model.set_hue(angle);
+/// if drag_released {
+///     model.commit();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PickerModel {
+    original: OklchA,
+    current: OklchA,
+    gamut: GamutPolicy,
+    snap: Snap,
+    constraint: Option<ColorConstraint>,
+    history: Vec<OklchA>,
+    history_len: usize,
+}
+
+impl PickerModel {
+    /// Starts editing `color`, keeping a history of up to 16 committed colors.
+    pub fn new(color: OklchA) -> Self {
+        Self {
+            original: color,
+            current: color,
+            gamut: GamutPolicy::default(),
+            snap: Snap::default(),
+            constraint: None,
+            history: Vec::new(),
+            history_len: 16,
+        }
+    }
+
+    pub fn with_gamut_policy(mut self, gamut: GamutPolicy) -> Self {
+        self.gamut = gamut;
+        self
+    }
+
+    /// Snap edited colors to discrete steps, see [`Snap`].
+    pub fn with_snap(mut self, snap: Snap) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// Keep edits within `constraint`, see [`ColorConstraint`].
+    pub fn with_constraint(mut self, constraint: ColorConstraint) -> Self {
+        self.constraint = Some(constraint);
+        self
+    }
+
+    /// How many committed colors [`history`](Self::history) keeps.
+    pub fn with_history_len(mut self, len: usize) -> Self {
+        self.history_len = len;
+        self.history.drain(..self.history.len().saturating_sub(len));
+        self
+    }
+
+    /// The color being edited.
+    pub fn color(&self) -> OklchA {
+        self.current
+    }
+
+    /// The color as of the last [`commit`](Self::commit), or when editing started.
+    pub fn original(&self) -> OklchA {
+        self.original
+    }
+
+    /// Whether the color changed since the last commit.
+    pub fn is_modified(&self) -> bool {
+        self.current != self.original
+    }
+
+    /// Sets the color being edited, after applying the gamut policy, snapping and the
+    /// constraint. Returns the resulting color.
+    pub fn set_color(&mut self, color: OklchA) -> OklchA {
+        self.current = constrain(self.current, color, self.gamut, &self.snap, self.constraint.as_ref());
+        self.current
+    }
+
+    /// Sets the Oklch lightness, clamped to `0..=1`.
+    pub fn set_lightness(&mut self, l: f32) -> OklchA {
+        let mut color = self.current;
+        color.col.l = l.clamp(0.0, 1.0);
+        self.set_color(color)
+    }
+
+    pub fn set_chroma(&mut self, c: f32) -> OklchA {
+        let mut color = self.current;
+        color.col.c = c.max(0.0);
+        self.set_color(color)
+    }
+
+    /// Sets the hue, in radians.
+    pub fn set_hue(&mut self, h: f32) -> OklchA {
+        let mut color = self.current;
+        color.col.h = convert::wrap_hue(h);
+        self.set_color(color)
+    }
+
+    pub fn set_alpha(&mut self, alpha: f32) -> OklchA {
+        let mut color = self.current;
+        color.alpha = alpha.clamp(0.0, 1.0);
+        self.set_color(color)
+    }
+
+    /// Accepts the current color: it becomes the [`original`](Self::original) and the newest
+    /// history entry.
+    pub fn commit(&mut self) -> OklchA {
+        self.original = self.current;
+        push_history(&mut self.history, self.current, self.history_len);
+        self.current
+    }
+
+    /// Goes back to the [`original`](Self::original) color.
+    pub fn cancel(&mut self) -> OklchA {
+        self.current = self.original;
+        self.current
+    }
+
+    /// The committed colors, oldest first.
+    pub fn history(&self) -> &[OklchA] {
+        &self.history
+    }
+
+    /// Makes history entry `index` the color being edited, without committing it.
+    pub fn restore(&mut self, index: usize) -> Option<OklchA> {
+        let color = *self.history.get(index)?;
+        Some(self.set_color(color))
+    }
+}
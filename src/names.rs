@@ -0,0 +1,130 @@
+//! Rough names for colors, for tooltips: "this is a kind of teal".
+
+use crate::{convert, OklchA};
+
+/// A deliberately short list of broad color names (CSS values), so that the nearest one is a
+/// useful description rather than a guess between near-identical shades.
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("black", [0x00, 0x00, 0x00]),
+    ("dim gray", [0x69, 0x69, 0x69]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("dark green", [0x00, 0x64, 0x00]),
+    ("sea green", [0x2e, 0x8b, 0x57]),
+    ("mint", [0x98, 0xff, 0x98]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("sky blue", [0x87, 0xce, 0xeb]),
+    ("steel blue", [0x46, 0x82, 0xb4]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("hot pink", [0xff, 0x69, 0xb4]),
+];
+
+/// The name of the color in [`NAMED_COLORS`] closest to `color` in Oklab, ignoring alpha.
+pub(crate) fn nearest_color_name(color: OklchA) -> &'static str {
+    NAMED_COLORS
+        .iter()
+        .map(|&(name, [r, g, b])| (name, convert::delta_e_ok(convert::srgb8_to_oklch([r, g, b, 255]), color)))
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(name, _)| name)
+        .unwrap_or("black")
+}
+
+/// Hue families and their Oklch hue in degrees, as in common UI palettes.
+const HUE_FAMILIES: &[(&str, f32)] = &[
+    ("Rose", 16.0),
+    ("Red", 25.0),
+    ("Orange", 47.0),
+    ("Amber", 70.0),
+    ("Yellow", 86.0),
+    ("Lime", 131.0),
+    ("Green", 149.0),
+    ("Emerald", 163.0),
+    ("Teal", 182.0),
+    ("Cyan", 215.0),
+    ("Sky", 237.0),
+    ("Blue", 259.0),
+    ("Indigo", 277.0),
+    ("Violet", 293.0),
+    ("Purple", 304.0),
+    ("Fuchsia", 322.0),
+    ("Pink", 354.0),
+];
+
+/// Lightness tiers (50 lightest, 950 darkest) and their Oklch lightness.
+const LIGHTNESS_TIERS: &[(u16, f32)] = &[
+    (50, 0.97),
+    (100, 0.94),
+    (200, 0.89),
+    (300, 0.82),
+    (400, 0.72),
+    (500, 0.64),
+    (600, 0.56),
+    (700, 0.49),
+    (800, 0.42),
+    (900, 0.37),
+    (950, 0.27),
+];
+
+/// Below this chroma a color counts as gray, and below twice this as tinted gray.
+const GRAY_CHROMA: f32 = 0.02;
+
+/// A readable name from the hue family and lightness tier of `color`, e.g. "Blue 500", with
+/// "Gray", "Slate" (cool) and "Stone" (warm) for the grays.
+pub(crate) fn generated_name(color: OklchA) -> String {
+    use std::cmp::Ordering;
+
+    let h = color.col.h.to_degrees().rem_euclid(360.0);
+    let family = if color.col.c < GRAY_CHROMA {
+        "Gray"
+    } else if color.col.c < 2.0 * GRAY_CHROMA {
+        if (90.0..300.0).contains(&h) {
+            "Slate"
+        } else {
+            "Stone"
+        }
+    } else {
+        let hue_distance = |center: f32| {
+            let d = (h - center).rem_euclid(360.0);
+            d.min(360.0 - d)
+        };
+        HUE_FAMILIES
+            .iter()
+            .min_by(|(_, a), (_, b)| hue_distance(*a).partial_cmp(&hue_distance(*b)).unwrap_or(Ordering::Equal))
+            .map(|&(name, _)| name)
+            .unwrap_or("Gray")
+    };
+    let tier = LIGHTNESS_TIERS
+        .iter()
+        .min_by(|(_, a), (_, b)| {
+            (a - color.col.l).abs().partial_cmp(&(b - color.col.l).abs()).unwrap_or(Ordering::Equal)
+        })
+        .map(|&(tier, _)| tier)
+        .unwrap_or(500);
+    format!("{} {}", family, tier)
+}
@@ -0,0 +1,367 @@
+//! Named color palettes and a swatch grid widget to pick from them.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use crate::{background_checkers, convert, output_transform, Backdrop, OklchA, Srgba};
+
+#[cfg(feature = "ase")]
+mod ase;
+mod categorical;
+mod compare;
+mod css;
+mod extract;
+#[cfg(feature = "gpl")]
+mod gpl;
+#[cfg(feature = "json")]
+mod json;
+
+pub use categorical::{generate_categorical, CategoricalConstraints};
+pub use compare::{delta_e_heatmap, delta_e_matrix, DeltaEHeatmap};
+pub use extract::extract_palette;
+
+/// Error returned when reading a palette file fails.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteParseError {
+    /// The data doesn't start with the signature of the expected format.
+    InvalidHeader,
+    /// The entry at this (1-based) line couldn't be parsed.
+    InvalidEntry(usize),
+    /// The data ended in the middle of an entry.
+    UnexpectedEof,
+    /// An `.ase` color model other than RGB, CMYK, LAB or Gray.
+    UnsupportedColorModel([u8; 4]),
+}
+
+impl std::fmt::Display for PaletteParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteParseError::InvalidHeader => write!(f, "not a palette file of the expected format"),
+            PaletteParseError::InvalidEntry(line) => write!(f, "invalid palette entry on line {}", line),
+            PaletteParseError::UnexpectedEof => write!(f, "unexpected end of palette data"),
+            PaletteParseError::UnsupportedColorModel(model) => {
+                write!(f, "unsupported color model {:?}", String::from_utf8_lossy(model))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PaletteParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteEntry {
+    pub name: String,
+    pub color: OklchA,
+}
+
+/// A readable name for `color` from its hue family and lightness tier, like "Blue 500" or
+/// "Slate 300", for naming generated palette entries.
+pub fn auto_name(color: OklchA) -> String {
+    crate::names::generated_name(color)
+}
+
+/// An ordered list of named colors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    pub entries: Vec<PaletteEntry>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, name: impl Into<String>, color: OklchA) {
+        self.entries.push(PaletteEntry {
+            name: name.into(),
+            color,
+        });
+    }
+
+    /// Adds `color` named after its hue family and lightness tier, see [`auto_name`], with a
+    /// number appended if an entry already has that name.
+    pub fn push_auto_named(&mut self, color: OklchA) {
+        let base = auto_name(color);
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.entries.iter().any(|entry| entry.name == name) {
+            name = format!("{} ({})", base, n);
+            n += 1;
+        }
+        self.push(name, color);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Index of the entry closest to `color` in Oklab (see [`convert::delta_e_ok`]).
+    pub fn nearest(&self, color: OklchA) -> Option<usize> {
+        self.entries
+            .iter()
+            .map(|entry| convert::delta_e_ok(entry.color, color))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    }
+
+    /// Moves the entry at `from` so that it ends up at index `to`.
+    pub fn move_entry(&mut self, from: usize, to: usize) {
+        let entry = self.entries.remove(from);
+        self.entries.insert(to, entry);
+    }
+}
+
+/// Ordering used by [`Palette::sort_by`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SortKey {
+    /// Around the hue circle, starting at red. Near-grays come first, dark to light.
+    Hue,
+    /// Dark to light.
+    Lightness,
+    /// Dull to vivid.
+    Chroma,
+    /// A short path through Oklab, so that neighboring entries are as similar as possible.
+    /// Starts from the darkest entry; good for ramps and for palettes without a clear axis.
+    SmoothPath,
+}
+
+/// Chroma below which the hue of a color is too unstable to sort by.
+const ACHROMATIC_CHROMA: f32 = 0.02;
+
+impl Palette {
+    /// Reorders the entries according to `key`.
+    pub fn sort_by(&mut self, key: SortKey) {
+        let by = |a: f32, b: f32| a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal);
+        match key {
+            SortKey::Hue => self.entries.sort_by(|a, b| {
+                let (a, b) = (&a.color, &b.color);
+                let a_gray = a.col.c < ACHROMATIC_CHROMA;
+                let b_gray = b.col.c < ACHROMATIC_CHROMA;
+                b_gray.cmp(&a_gray).then_with(|| {
+                    if a_gray {
+                        by(a.col.l, b.col.l)
+                    } else {
+                        by(a.col.h.rem_euclid(std::f32::consts::TAU), b.col.h.rem_euclid(std::f32::consts::TAU))
+                            .then_with(|| by(a.col.l, b.col.l))
+                    }
+                })
+            }),
+            SortKey::Lightness => self.entries.sort_by(|a, b| by(a.color.col.l, b.color.col.l)),
+            SortKey::Chroma => self.entries.sort_by(|a, b| by(a.color.col.c, b.color.col.c)),
+            SortKey::SmoothPath => self.sort_smooth_path(),
+        }
+    }
+
+    /// Greedy nearest-neighbor tour followed by 2-opt improvement. Not optimal, but palettes
+    /// are small and the result looks right far more often than any single-axis sort.
+    fn sort_smooth_path(&mut self) {
+        let n = self.entries.len();
+        if n < 3 {
+            self.sort_by(SortKey::Lightness);
+            return;
+        }
+        let dist = |a: usize, b: usize| convert::delta_e_ok(self.entries[a].color, self.entries[b].color);
+
+        let start = (0..n)
+            .min_by(|&a, &b| {
+                let (a, b) = (self.entries[a].color.col.l, self.entries[b].color.col.l);
+                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(0);
+        let mut path = vec![start];
+        let mut remaining: Vec<usize> = (0..n).filter(|&i| i != start).collect();
+        while !remaining.is_empty() {
+            let last = *path.last().unwrap();
+            let (k, _) = remaining
+                .iter()
+                .enumerate()
+                .min_by(|a, b| {
+                    dist(last, *a.1).partial_cmp(&dist(last, *b.1)).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            path.push(remaining.swap_remove(k));
+        }
+
+        // 2-opt on an open path: reversing path[i..=j] only changes the edges at either end.
+        // The first entry stays put, so the path keeps starting from the darkest color.
+        for _ in 0..n {
+            let mut improved = false;
+            for i in 1..n - 1 {
+                for j in i + 1..n {
+                    let before = dist(path[i - 1], path[i])
+                        + if j + 1 < n { dist(path[j], path[j + 1]) } else { 0.0 };
+                    let after = dist(path[i - 1], path[j])
+                        + if j + 1 < n { dist(path[i], path[j + 1]) } else { 0.0 };
+                    if after + 1e-6 < before {
+                        path[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        let mut entries: Vec<Option<PaletteEntry>> = self.entries.drain(..).map(Some).collect();
+        self.entries = path.into_iter().filter_map(|i| entries[i].take()).collect();
+    }
+}
+
+/// Where index `index` ends up after [`Palette::move_entry`]`(from, to)`.
+fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+fn swatch(ui: &mut Ui, color: OklchA, selected: bool, sense: Sense) -> Response {
+    let size = Vec2::splat(ui.spacing().interact_size.y * 1.5);
+    let (rect, response) = ui.allocate_exact_size(size, sense);
+    response.widget_info(|| WidgetInfo::selected(WidgetType::ColorButton, selected, ""));
+    let visuals = ui.style().interact(&response);
+
+    background_checkers(ui.painter(), rect, Backdrop::default());
+    let fill = output_transform(ui.ctx()).apply(color.convert::<Srgba>().saturate().into_cint().into());
+    ui.painter().rect_filled(rect, 0.0, fill);
+
+    let stroke = if selected {
+        Stroke::new(2.0, ui.visuals().selection.stroke.color)
+    } else {
+        visuals.bg_stroke
+    };
+    ui.painter().rect_stroke(rect.expand(visuals.expansion), 0.0, stroke);
+
+    response
+}
+
+/// A wrapping grid of swatches showing a [`Palette`], with click to select and optionally
+/// drag-and-drop reordering.
+///
+/// ```ignore
+/// PaletteGrid::new(&mut palette, &mut selected).reorderable(true).show(ui);
+/// ```
+pub struct PaletteGrid<'a> {
+    palette: &'a mut Palette,
+    selected: &'a mut Option<usize>,
+    reorderable: bool,
+    hovered: Option<&'a mut Option<usize>>,
+}
+
+impl<'a> PaletteGrid<'a> {
+    pub fn new(palette: &'a mut Palette, selected: &'a mut Option<usize>) -> Self {
+        Self {
+            palette,
+            selected,
+            reorderable: false,
+            hovered: None,
+        }
+    }
+
+    /// Set `hovered` to the index of the hovered swatch, if any, e.g. to preview its color.
+    pub fn hovered(mut self, hovered: &'a mut Option<usize>) -> Self {
+        self.hovered = Some(hovered);
+        self
+    }
+
+    /// Allow dragging swatches onto each other to reorder the palette.
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let Self {
+            palette,
+            selected,
+            reorderable,
+            mut hovered,
+        } = self;
+        if let Some(hovered) = hovered.as_deref_mut() {
+            *hovered = None;
+        }
+
+        let drag_id = ui.make_persistent_id("palette_grid_drag");
+        let mut dragged = temp_get!(ui, drag_id, usize);
+        let mut drop_target = None;
+        let mut changed = false;
+
+        let sense = if reorderable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let pointer_pos = ui.input().pointer.interact_pos();
+
+        let mut response = ui
+            .horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing = Vec2::splat(2.0);
+                for (i, entry) in palette.entries.iter().enumerate() {
+                    let response = swatch(ui, entry.color, *selected == Some(i), sense)
+                        .on_hover_text(format!("{}\n{}", entry.name, convert::to_hex(entry.color)));
+                    if response.clicked() && *selected != Some(i) {
+                        *selected = Some(i);
+                        changed = true;
+                    }
+                    if response.drag_started() {
+                        dragged = Some(i);
+                    }
+                    if response.hovered() {
+                        if let Some(hovered) = hovered.as_deref_mut() {
+                            *hovered = Some(i);
+                        }
+                    }
+                    if pointer_pos.map_or(false, |pos| response.rect.contains(pos)) {
+                        drop_target = Some(i);
+                    }
+                }
+            })
+            .response;
+
+        if let Some(from) = dragged {
+            if ui.input().pointer.any_released() {
+                if let Some(to) = drop_target.filter(|&to| to != from) {
+                    palette.move_entry(from, to);
+                    *selected = selected.map(|index| moved_index(index, from, to));
+                    changed = true;
+                }
+                dragged = None;
+            }
+        }
+
+        if let Some(index) = dragged {
+            temp_insert!(ui, drag_id, index);
+        } else {
+            temp_remove!(ui, drag_id, usize);
+        }
+
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+impl Widget for PaletteGrid<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui)
+    }
+}
+
+/// Shows `palette` as a grid of swatches; clicking one selects it.
+/// See [`PaletteGrid`] for more options.
+pub fn palette_grid(ui: &mut Ui, palette: &mut Palette, selected: &mut Option<usize>) -> Response {
+    PaletteGrid::new(palette, selected).show(ui)
+}
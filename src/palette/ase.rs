@@ -0,0 +1,127 @@
+//! Adobe Swatch Exchange (`.ase`) files.
+
+use colstodian::*;
+
+use super::{Palette, PaletteParseError};
+use crate::{cielab, cmyk, EncodedSrgbA, WhitePoint};
+
+const SIGNATURE: &[u8] = b"ASEF";
+const COLOR_ENTRY: u16 = 0x0001;
+/// "Normal" (as opposed to global or spot) color.
+const COLOR_TYPE_NORMAL: u16 = 2;
+
+/// Big-endian cursor over the file.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PaletteParseError> {
+        if self.bytes.len() < n {
+            return Err(PaletteParseError::UnexpectedEof);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, PaletteParseError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, PaletteParseError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32, PaletteParseError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+}
+
+impl Palette {
+    /// Parses an Adobe `.ase` swatch file. Groups are flattened; RGB, CMYK (naively),
+    /// LAB (D50) and Gray colors are supported.
+    pub fn from_ase(bytes: &[u8]) -> Result<Self, PaletteParseError> {
+        let mut reader = Reader { bytes };
+        if reader.take(4).ok() != Some(SIGNATURE) {
+            return Err(PaletteParseError::InvalidHeader);
+        }
+        let _version = (reader.u16()?, reader.u16()?);
+        let block_count = reader.u32()?;
+
+        let mut palette = Palette::new();
+        for _ in 0..block_count {
+            let kind = reader.u16()?;
+            let len = reader.u32()? as usize;
+            let mut block = Reader {
+                bytes: reader.take(len)?,
+            };
+            if kind != COLOR_ENTRY {
+                continue; // group start/end
+            }
+
+            let name_len = block.u16()? as usize;
+            let name = (0..name_len)
+                .map(|_| block.u16())
+                .collect::<Result<Vec<_>, _>>()?;
+            let name = String::from_utf16_lossy(&name)
+                .trim_end_matches('\0')
+                .to_owned();
+
+            let mut model = [0; 4];
+            model.copy_from_slice(block.take(4)?);
+            let color = match &model {
+                b"RGB " => {
+                    let (r, g, b) = (block.f32()?, block.f32()?, block.f32()?);
+                    EncodedSrgbA::new(r, g, b, 1.0).convert()
+                }
+                b"CMYK" => {
+                    let cmyk = [block.f32()?, block.f32()?, block.f32()?, block.f32()?];
+                    cmyk::cmyk_to_oklch(cmyk, 1.0)
+                }
+                b"LAB " => {
+                    let (l, a, b) = (block.f32()?, block.f32()?, block.f32()?);
+                    cielab::lab_to_oklch([l * 100.0, a, b], 1.0, WhitePoint::D50)
+                }
+                b"Gray" => {
+                    let gray = block.f32()?;
+                    EncodedSrgbA::new(gray, gray, gray, 1.0).convert()
+                }
+                _ => return Err(PaletteParseError::UnsupportedColorModel(model)),
+            };
+            palette.push(name, color);
+        }
+        Ok(palette)
+    }
+
+    /// Writes an Adobe `.ase` swatch file with one RGB color per entry. Alpha is dropped,
+    /// as the format has none.
+    pub fn to_ase(&self) -> Vec<u8> {
+        let mut out = SIGNATURE.to_vec();
+        out.extend_from_slice(&1u16.to_be_bytes());
+        out.extend_from_slice(&0u16.to_be_bytes());
+        out.extend_from_slice(&(self.entries.len() as u32).to_be_bytes());
+
+        for entry in &self.entries {
+            let mut block = Vec::new();
+            let name: Vec<u16> = entry.name.encode_utf16().chain(std::iter::once(0)).collect();
+            block.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            for unit in name {
+                block.extend_from_slice(&unit.to_be_bytes());
+            }
+            block.extend_from_slice(b"RGB ");
+            let srgb = entry.color.convert::<EncodedSrgbA>().saturate();
+            for channel in &[srgb.col.r, srgb.col.g, srgb.col.b] {
+                block.extend_from_slice(&channel.to_bits().to_be_bytes());
+            }
+            block.extend_from_slice(&COLOR_TYPE_NORMAL.to_be_bytes());
+
+            out.extend_from_slice(&COLOR_ENTRY.to_be_bytes());
+            out.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            out.extend_from_slice(&block);
+        }
+        out
+    }
+}
@@ -0,0 +1,82 @@
+//! Distinct colors for categories, e.g. the series of a chart.
+
+use std::ops::RangeInclusive;
+
+use crate::{convert, OklchA};
+
+/// Limits on the colors [`generate_categorical`] picks from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoricalConstraints {
+    /// Oklch lightness. A narrow range keeps the colors equally prominent.
+    pub lightness: RangeInclusive<f32>,
+    /// Oklch chroma, further limited by the sRGB gamut.
+    pub chroma: RangeInclusive<f32>,
+}
+
+impl Default for CategoricalConstraints {
+    fn default() -> Self {
+        Self {
+            lightness: 0.5..=0.8,
+            chroma: 0.08..=0.2,
+        }
+    }
+}
+
+const LIGHTNESS_STEPS: usize = 10;
+const CHROMA_STEPS: usize = 5;
+const HUE_STEPS: usize = 72;
+
+/// `n` colors within `constraints` that are as far apart from each other in Oklab as possible,
+/// in the order they were picked: any prefix is itself a well separated set.
+///
+/// Picks greedily from a grid of in-gamut candidates, each time taking the candidate farthest
+/// from all colors picked so far. Deterministic, and fine for the handful of categories a chart
+/// can show; the smallest pairwise [`delta_e_ok`](convert::delta_e_ok) shrinks as `n` grows.
+pub fn generate_categorical(n: usize, constraints: &CategoricalConstraints) -> Vec<OklchA> {
+    profile_function!();
+    let (l_min, l_max) = (*constraints.lightness.start(), *constraints.lightness.end());
+    let (c_min, c_max) = (*constraints.chroma.start(), *constraints.chroma.end());
+    let step = |min: f32, max: f32, i: usize, steps: usize| min + (max - min) * i as f32 / steps as f32;
+
+    let mut candidates = Vec::new();
+    for li in 0..=LIGHTNESS_STEPS {
+        let l = step(l_min, l_max, li, LIGHTNESS_STEPS);
+        for hi in 0..HUE_STEPS {
+            let h = convert::wrap_hue(hi as f32 / HUE_STEPS as f32 * std::f32::consts::TAU);
+            let gamut_max = convert::max_chroma_for(l, h);
+            for ci in 0..=CHROMA_STEPS {
+                let c = step(c_min, c_max, ci, CHROMA_STEPS);
+                if c <= gamut_max {
+                    candidates.push(OklchA::new(l, c, h, 1.0));
+                }
+            }
+        }
+    }
+
+    // Start from the most colorful candidate, then repeatedly add the farthest one.
+    let first = candidates
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.col.c.partial_cmp(&b.col.c).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i);
+    let mut picked = Vec::with_capacity(n);
+    let mut min_dist = vec![f32::INFINITY; candidates.len()];
+    let mut next = first;
+    while let Some(i) = next {
+        if picked.len() >= n {
+            break;
+        }
+        let color = candidates[i];
+        picked.push(color);
+        for (d, candidate) in min_dist.iter_mut().zip(&candidates) {
+            *d = d.min(convert::delta_e_ok(*candidate, color));
+        }
+        next = min_dist
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d > 0.0)
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i);
+    }
+    picked
+}
@@ -0,0 +1,148 @@
+//! Comparing two palettes by the color differences between their entries.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use super::Palette;
+use crate::{best_text_color, convert, output_transform, Colormap, MinContrast, OklchA, Srgba};
+
+/// The Oklab ΔE (see [`convert::delta_e_ok`]) between each entry of `a` (rows) and each entry
+/// of `b` (columns).
+pub fn delta_e_matrix(a: &Palette, b: &Palette) -> Vec<Vec<f32>> {
+    a.entries
+        .iter()
+        .map(|row| b.entries.iter().map(|col| convert::delta_e_ok(row.color, col.color)).collect())
+        .collect()
+}
+
+/// A heatmap of the [`delta_e_matrix`] of two palettes: the entries of `a` down the left, those
+/// of `b` along the top, and each cell colored by the difference of its pair. Cells pairing
+/// entries at the same index are outlined, e.g. to check that a dark-mode palette keeps the
+/// relationships of the light-mode one it was derived from.
+///
+/// ```ignore
+/// DeltaEHeatmap::new(&light, &dark).max_delta_e(0.3).show(ui);
+/// ```
+pub struct DeltaEHeatmap<'a> {
+    a: &'a Palette,
+    b: &'a Palette,
+    colormap: Colormap,
+    max_delta_e: Option<f32>,
+    show_values: bool,
+}
+
+impl<'a> DeltaEHeatmap<'a> {
+    pub fn new(a: &'a Palette, b: &'a Palette) -> Self {
+        Self {
+            a,
+            b,
+            colormap: Colormap::Viridis,
+            max_delta_e: None,
+            show_values: true,
+        }
+    }
+
+    /// The colormap cells are colored with, from ΔE 0 to [`max_delta_e`](Self::max_delta_e).
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The ΔE at the top of the colormap, so that heatmaps of different palettes compare.
+    /// Defaults to the largest ΔE in the matrix.
+    pub fn max_delta_e(mut self, max: f32) -> Self {
+        self.max_delta_e = Some(max);
+        self
+    }
+
+    /// Write each cell's ΔE into it (the default). It is always shown when hovering a cell.
+    pub fn show_values(mut self, show: bool) -> Self {
+        self.show_values = show;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let matrix = delta_e_matrix(self.a, self.b);
+        let (rows, cols) = (self.a.len(), self.b.len());
+        let max = self
+            .max_delta_e
+            .unwrap_or_else(|| matrix.iter().flatten().copied().fold(0.0, f32::max))
+            .max(f32::EPSILON);
+        let gradient = self.colormap.gradient();
+        let output = output_transform(ui.ctx());
+        let to_color32 = |color: OklchA| -> Color32 {
+            output.apply(color.convert::<Srgba>().saturate().into_cint().into())
+        };
+
+        let cell = if self.show_values {
+            ui.spacing().interact_size * vec2(1.0, 0.75)
+        } else {
+            Vec2::splat(ui.spacing().interact_size.y)
+        };
+        let label = Vec2::splat(ui.spacing().interact_size.y * 0.75);
+        let size = vec2(label.x + cols as f32 * cell.x, label.y + rows as f32 * cell.y);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        let cell_rect = |row: usize, col: usize| {
+            let min = rect.min + label + vec2(col as f32 * cell.x, row as f32 * cell.y);
+            Rect::from_min_size(min, cell)
+        };
+
+        let painter = ui.painter();
+        for (col, entry) in self.b.entries.iter().enumerate() {
+            let min = pos2(cell_rect(0, col).left(), rect.top());
+            let swatch = Rect::from_min_size(min, vec2(cell.x, label.y)).shrink(1.0);
+            painter.rect_filled(swatch, 0.0, to_color32(entry.color).to_opaque());
+        }
+        for (row, entry) in self.a.entries.iter().enumerate() {
+            let min = pos2(rect.left(), cell_rect(row, 0).top());
+            let swatch = Rect::from_min_size(min, vec2(label.x, cell.y)).shrink(1.0);
+            painter.rect_filled(swatch, 0.0, to_color32(entry.color).to_opaque());
+        }
+
+        let candidates = [Color32::WHITE, Color32::BLACK];
+        for (row, values) in matrix.iter().enumerate() {
+            for (col, &delta_e) in values.iter().enumerate() {
+                let cell = cell_rect(row, col);
+                let fill = to_color32(gradient.sample(delta_e / max));
+                painter.rect_filled(cell, 0.0, fill);
+                if self.show_values {
+                    let text = best_text_color(fill, &candidates, MinContrast::Wcag2(4.5));
+                    let value = format!("{:.2}", delta_e);
+                    painter.text(cell.center(), Align2::CENTER_CENTER, value, TextStyle::Small, text);
+                }
+                if row == col {
+                    painter.rect_stroke(cell.shrink(0.5), 0.0, ui.visuals().selection.stroke);
+                }
+            }
+        }
+
+        let hovered = response.hover_pos().and_then(|pos| {
+            let offset = pos - rect.min - label;
+            if offset.x < 0.0 || offset.y < 0.0 {
+                return None;
+            }
+            let (row, col) = ((offset.y / cell.y) as usize, (offset.x / cell.x) as usize);
+            Some((row, col)).filter(|_| row < rows && col < cols)
+        });
+        match hovered {
+            Some((row, col)) => {
+                painter.rect_stroke(cell_rect(row, col), 0.0, ui.visuals().widgets.hovered.fg_stroke);
+                let (a, b) = (&self.a.entries[row], &self.b.entries[col]);
+                response.on_hover_text(format!("{} vs {}: ΔE {:.3}", a.name, b.name, matrix[row][col]))
+            }
+            None => response,
+        }
+    }
+}
+
+impl Widget for DeltaEHeatmap<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui)
+    }
+}
+
+/// Shows the ΔE heatmap of palettes `a` and `b`. See [`DeltaEHeatmap`] for more options.
+pub fn delta_e_heatmap(ui: &mut Ui, a: &Palette, b: &Palette) -> Response {
+    DeltaEHeatmap::new(a, b).show(ui)
+}
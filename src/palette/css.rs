@@ -0,0 +1,57 @@
+//! CSS custom properties (`--prefix-name: oklch(...)`).
+
+use super::{Palette, PaletteParseError};
+use crate::convert;
+
+/// Lowercase, with runs of anything but ASCII letters and digits replaced by a single `-`.
+fn css_identifier(name: &str) -> String {
+    let mut ident = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            ident.push(c.to_ascii_lowercase());
+        } else if !ident.is_empty() && !ident.ends_with('-') {
+            ident.push('-');
+        }
+    }
+    ident.trim_end_matches('-').to_owned()
+}
+
+impl Palette {
+    /// A `:root { ... }` block defining one `--{prefix}-{name}: oklch(...)` custom property per
+    /// entry, with names converted to CSS identifiers (`"Warm Coral"` becomes `warm-coral`).
+    pub fn to_css_variables(&self, prefix: &str) -> String {
+        let mut out = String::from(":root {\n");
+        for entry in &self.entries {
+            out += &format!(
+                "  --{}-{}: {};\n",
+                prefix,
+                css_identifier(&entry.name),
+                convert::to_css_oklch(entry.color)
+            );
+        }
+        out += "}\n";
+        out
+    }
+
+    /// Reads back the custom properties written by [`Palette::to_css_variables`]: every
+    /// `--{prefix}-{name}: oklch(...);` declaration becomes an entry called `name`.
+    /// Other declarations are ignored.
+    pub fn from_css_variables(css: &str, prefix: &str) -> Result<Self, PaletteParseError> {
+        let var_prefix = format!("--{}-", prefix);
+        let mut palette = Palette::new();
+        for (i, line) in css.lines().enumerate() {
+            let line = line.trim();
+            let declaration = match line.strip_prefix(&var_prefix) {
+                Some(declaration) => declaration,
+                None => continue,
+            };
+            let invalid = PaletteParseError::InvalidEntry(i + 1);
+            let colon = declaration.find(':').ok_or_else(|| invalid.clone())?;
+            let (name, value) = (&declaration[..colon], &declaration[colon + 1..]);
+            let value = value.trim().trim_end_matches(';');
+            let color = convert::from_css_oklch(value).ok_or(invalid)?;
+            palette.push(name.trim(), color);
+        }
+        Ok(palette)
+    }
+}
@@ -0,0 +1,108 @@
+//! Dominant colors of an image, by k-means clustering in Oklab.
+
+use egui::Color32;
+
+use cint::ColorInterop;
+
+use crate::{OklabA, OklchA, Srgba};
+
+/// At most this many pixels take part in the clustering; larger images are sampled with a stride.
+const MAX_SAMPLES: usize = 16_384;
+const MAX_ITERATIONS: usize = 24;
+
+fn distance_sq(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    let (dl, da, db) = (a[0] - b[0], a[1] - b[1], a[2] - b[2]);
+    dl * dl + da * da + db * db
+}
+
+fn nearest(centers: &[[f32; 3]], p: &[f32; 3]) -> usize {
+    centers
+        .iter()
+        .map(|c| distance_sq(c, p))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Up to `k` representative colors of `pixels` (e.g. the pixels of an image, row by row),
+/// most common first. Pixels that are less than half opaque are ignored.
+///
+/// Clustering happens in Oklab, so the result groups colors the way they look rather than by
+/// their RGB values. It is deterministic: the same pixels always give the same palette.
+pub fn extract_palette(pixels: &[Color32], k: usize) -> Vec<OklchA> {
+    profile_function!();
+    let stride = (pixels.len() / MAX_SAMPLES).max(1);
+    let samples: Vec<[f32; 3]> = pixels
+        .iter()
+        .step_by(stride)
+        .filter(|p| p.a() >= 128)
+        .map(|p| {
+            let lab: OklabA = Srgba::from(p.into_cint()).convert();
+            [lab.col.l, lab.col.a, lab.col.b]
+        })
+        .collect();
+    if samples.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    // Farthest-point initialization, starting from the sample closest to the mean.
+    let n = samples.len() as f32;
+    let mean = samples.iter().fold([0.0; 3], |acc, p| {
+        [acc[0] + p[0] / n, acc[1] + p[1] / n, acc[2] + p[2] / n]
+    });
+    let mut centers = vec![samples[nearest(&samples, &mean)]];
+    let mut min_dist: Vec<f32> = samples.iter().map(|p| distance_sq(p, &centers[0])).collect();
+    while centers.len() < k {
+        let (far, &dist) = min_dist
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap();
+        if dist == 0.0 {
+            // Fewer distinct colors than `k`.
+            break;
+        }
+        let center = samples[far];
+        for (d, p) in min_dist.iter_mut().zip(&samples) {
+            *d = d.min(distance_sq(p, &center));
+        }
+        centers.push(center);
+    }
+
+    let mut assignment = vec![usize::MAX; samples.len()];
+    let mut counts = vec![0usize; centers.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut moved = false;
+        for (a, p) in assignment.iter_mut().zip(&samples) {
+            let i = nearest(&centers, p);
+            if *a != i {
+                *a = i;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+
+        let mut sums = vec![[0.0f32; 3]; centers.len()];
+        counts = vec![0; centers.len()];
+        for (&a, p) in assignment.iter().zip(&samples) {
+            sums[a] = [sums[a][0] + p[0], sums[a][1] + p[1], sums[a][2] + p[2]];
+            counts[a] += 1;
+        }
+        for ((center, sum), &count) in centers.iter_mut().zip(&sums).zip(&counts) {
+            if count > 0 {
+                let count = count as f32;
+                *center = [sum[0] / count, sum[1] / count, sum[2] / count];
+            }
+        }
+    }
+
+    let mut clusters: Vec<_> = centers.into_iter().zip(counts).filter(|(_, count)| *count > 0).collect();
+    clusters.sort_by(|(_, a), (_, b)| b.cmp(a));
+    clusters
+        .into_iter()
+        .map(|([l, a, b], _)| OklabA::new(l, a, b, 1.0).convert())
+        .collect()
+}
@@ -0,0 +1,52 @@
+//! GIMP palette (`.gpl`) files.
+
+use colstodian::*;
+
+use super::{Palette, PaletteParseError};
+use crate::{convert, EncodedSrgbA};
+
+impl Palette {
+    /// Parses a GIMP `.gpl` palette. The palette's own `Name:` and `Columns:` are ignored.
+    pub fn from_gpl(bytes: &[u8]) -> Result<Self, PaletteParseError> {
+        let text = String::from_utf8_lossy(bytes);
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, header)) if header.trim() == "GIMP Palette" => {}
+            _ => return Err(PaletteParseError::InvalidHeader),
+        }
+
+        let mut palette = Palette::new();
+        for (i, line) in lines {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let mut channel = || {
+                parts
+                    .next()
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .ok_or(PaletteParseError::InvalidEntry(i + 1))
+            };
+            let rgba = [channel()?, channel()?, channel()?, 255];
+            let name = parts.collect::<Vec<_>>().join(" ");
+            palette.push(name, convert::srgb8_to_oklch(rgba));
+        }
+        Ok(palette)
+    }
+
+    /// Writes a GIMP `.gpl` palette called `name`. Alpha is dropped, as the format has none.
+    pub fn to_gpl(&self, name: &str) -> Vec<u8> {
+        let mut out = format!("GIMP Palette\nName: {}\n#\n", name);
+        for entry in &self.entries {
+            let [r, g, b, _] = entry.color.convert::<EncodedSrgbA>().saturate().to_u8();
+            out += &format!("{:3} {:3} {:3}\t{}\n", r, g, b, entry.name);
+        }
+        out.into_bytes()
+    }
+}
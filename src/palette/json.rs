@@ -0,0 +1,49 @@
+//! JSON palettes: `{ "entries": [{ "name": ..., "l": ..., "c": ..., "h": ..., "alpha": ... }] }`,
+//! with the Oklch components stored exactly as in [`OklchA`](crate::OklchA) (hue in radians).
+
+use serde::{Deserialize, Serialize};
+
+use super::Palette;
+use crate::OklchA;
+
+#[derive(Serialize, Deserialize)]
+struct JsonEntry {
+    name: String,
+    l: f32,
+    c: f32,
+    h: f32,
+    alpha: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonPalette {
+    entries: Vec<JsonEntry>,
+}
+
+impl Palette {
+    pub fn to_json(&self) -> String {
+        let palette = JsonPalette {
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| JsonEntry {
+                    name: entry.name.clone(),
+                    l: entry.color.col.l,
+                    c: entry.color.col.c,
+                    h: entry.color.col.h,
+                    alpha: entry.color.alpha,
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&palette).expect("palette JSON serialization can't fail")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let json: JsonPalette = serde_json::from_str(json)?;
+        let mut palette = Palette::new();
+        for entry in json.entries {
+            palette.push(entry.name, OklchA::new(entry.l, entry.c, entry.h, entry.alpha));
+        }
+        Ok(palette)
+    }
+}
@@ -0,0 +1,1357 @@
+//! The color picker popup: [`ColorEditButton`], its options and [`SharedPicker`].
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use crate::sliders::MarkerStyle;
+use crate::{
+    color_button, compat, convert, names, render_quality, show_color, token, AlphaDisplay, AlphaPreview, Backdrop,
+    ColorConstraint, ColorValidator, Controls, HueUnit, NumberFormat, OklchA, Palette, RenderQuality, Scheme, Snap,
+    Srgba, SwatchStyle, WhitePoint,
+};
+
+mod modes;
+mod panels;
+
+pub(crate) use modes::color_picker_oklab_2d;
+pub(crate) use panels::{offset_oklch, oklch_delta};
+use modes::{color_picker_indexed, mode_picker_ui};
+use panels::{
+    adjust_ui, blend_preview_ui, cmyk_ui, compositing_ui, context_preview_ui, contrast_ui, diagnostics_ui,
+    gradient_pair_ui, harmonies_ui, history_ui, mixer_ui, nearest_palette_ui, palette_panel_ui, quick_adjust_ui,
+    record_history, surround_preview_ui, theme_preview_ui, PopupTab,
+};
+
+/// Sizes of the picker popup, see [`ColorEditButton::popup_width`],
+/// [`ColorEditButton::slider_width`] and [`ColorEditButton::area_size`]. Whatever is `None`
+/// follows the popup's [`Ui::spacing`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct PopupLayout {
+    pub width: Option<f32>,
+    pub slider_width: Option<f32>,
+    /// Side of the 2D areas; the slider width if `None`.
+    pub area_size: Option<f32>,
+}
+
+impl PopupLayout {
+    /// Applies the widths to the contents `ui` of the popup.
+    fn apply(self, ui: &mut Ui) {
+        if let Some(slider_width) = self.slider_width {
+            ui.spacing_mut().slider_width = slider_width;
+        }
+        if let Some(width) = self.width {
+            ui.set_width(width);
+        }
+    }
+}
+
+/// Makes the already shown color button `button_response` open a popup containing `picker`
+/// when clicked. `picker` should return `true` if it changed the color. Without
+/// `double_click_toggles`, the second click of a double-click leaves the popup as the first
+/// one left it, for buttons with a [`DoubleClickAction`].
+pub(crate) fn color_edit_popup(
+    ui: &mut Ui,
+    mut button_response: Response,
+    popup_id: Id,
+    layout: PopupLayout,
+    double_click_toggles: bool,
+    picker: impl FnOnce(&mut Ui) -> bool,
+) -> Response {
+    profile_function!();
+
+    let trap_id = popup_id.with("focus");
+
+    // While pinned, the picker lives in a window that stays open until unpinned or closed.
+    let pinned_id = popup_id.with("pinned");
+    let was_pinned = temp_get!(ui, pinned_id, bool).unwrap_or(false);
+    let mut pinned = was_pinned;
+
+    if button_response.clicked() && (double_click_toggles || !button_response.double_clicked()) {
+        if pinned {
+            pinned = false;
+        } else {
+            compat::toggle_popup(ui.ctx(), popup_id);
+        }
+    }
+
+    if was_pinned && pinned {
+        let mut window_open = true;
+        Window::new("Color")
+            .id(popup_id.with("window"))
+            .open(&mut window_open)
+            .resizable(false)
+            .default_pos(button_response.rect.max)
+            .show(ui.ctx(), |ui| {
+                layout.apply(ui);
+                trap_focus(ui, trap_id, |ui| {
+                    let first = pin_toggle_ui(ui, &mut pinned);
+                    if picker(ui) {
+                        button_response.mark_changed();
+                    }
+                    first
+                });
+            });
+        if !window_open {
+            pinned = false;
+        } else if !pinned {
+            // Unpinned with the toggle: go back to being a popup.
+            compat::open_popup(ui.ctx(), popup_id);
+        }
+    } else if compat::is_popup_open(ui.ctx(), popup_id) {
+        // TODO: make it easier to show a temporary popup that closes when you click outside it
+        // Constrained rather than placed at a fixed position, so that it can still be dragged.
+        let area_response = Area::new(popup_id)
+            .order(Order::Foreground)
+            .default_pos(button_response.rect.max)
+            .constrain(true)
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    layout.apply(ui);
+                    trap_focus(ui, trap_id, |ui| {
+                        let first = pin_toggle_ui(ui, &mut pinned);
+                        if picker(ui) {
+                            button_response.mark_changed();
+                        }
+                        first
+                    });
+                });
+            });
+
+        if pinned {
+            compat::close_popup(ui.ctx());
+        } else if !button_response.clicked() && ui.input().key_pressed(Key::Escape) {
+            compat::close_popup(ui.ctx());
+            compat::request_focus(ui.ctx(), button_response.id);
+        } else if !button_response.clicked() && area_response.clicked_elsewhere() {
+            compat::close_popup(ui.ctx());
+        }
+    }
+
+    if pinned {
+        temp_insert!(ui, pinned_id, true);
+    } else {
+        temp_remove!(ui, pinned_id, bool);
+    }
+    if !is_picker_shown(ui, popup_id) {
+        temp_remove!(ui, trap_id, FocusTrap);
+    }
+
+    button_response
+}
+
+/// Keyboard focus bookkeeping of an open popup, see [`trap_focus`].
+#[derive(Clone, Copy, Debug, Default)]
+struct FocusTrap {
+    /// The focused widget at the end of the last frame.
+    focus: Option<Id>,
+    /// Whether that widget is inside the popup.
+    inside: bool,
+    /// The widget to give focus to at the start of this frame, to wrap around.
+    wrap_to: Option<Id>,
+    /// The last widget in tab order, once focus has wrapped forward past it.
+    last: Option<Id>,
+}
+
+/// Keeps Tab and Shift+Tab cycling among the widgets `add_contents` adds, which returns the
+/// first of them. Focus moves to it on the first frame. egui moves focus in the order widgets
+/// are added, so focus leaving at the end (or start) is noticed after the fact and moved back
+/// at the start of the next frame.
+fn trap_focus(ui: &mut Ui, trap_id: Id, add_contents: impl FnOnce(&mut Ui) -> Id) {
+    let stored = temp_get!(ui, trap_id, FocusTrap);
+    let just_opened = stored.is_none();
+    let mut trap = stored.unwrap_or_default();
+
+    if let Some(target) = trap.wrap_to.take() {
+        compat::request_focus(ui.ctx(), target);
+        trap.inside = true;
+    } else if compat::focused(ui.ctx()) != trap.focus {
+        // Something outside took focus, e.g. a click next to a pinned window.
+        trap.inside = false;
+    }
+    let before = compat::focused(ui.ctx());
+
+    let first = add_contents(ui);
+
+    let after = compat::focused(ui.ctx());
+    let (tab, shift) = {
+        let input = ui.input();
+        (input.key_pressed(Key::Tab), input.modifiers.shift)
+    };
+    if just_opened {
+        compat::request_focus(ui.ctx(), first);
+        trap.inside = true;
+    } else if after != before {
+        if trap.inside && tab && !shift && after.is_none() {
+            // Tabbed past the last widget.
+            trap.last = before;
+            trap.wrap_to = Some(first);
+        } else if trap.inside && tab && shift && before == Some(first) {
+            // Shift+Tabbed back past the first widget.
+            trap.wrap_to = Some(trap.last.unwrap_or(first));
+        } else {
+            trap.inside = after.is_some();
+        }
+    }
+    trap.focus = compat::focused(ui.ctx());
+    temp_insert!(ui, trap_id, trap);
+}
+
+/// What double-clicking a color button does. With an action other than `None`, the second
+/// click runs it instead of closing the popup the first click opened.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DoubleClickAction {
+    None,
+    /// Set the color to this one, e.g. the default of a setting.
+    Reset(OklchA),
+    /// Copy the color as hex.
+    CopyHex,
+}
+
+impl Default for DoubleClickAction {
+    fn default() -> Self {
+        DoubleClickAction::None
+    }
+}
+
+/// What hovering a color button shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonTooltip {
+    None,
+    /// Just "Click to edit color".
+    Text,
+    /// A larger swatch with the hex code, the Oklch values and the name of the nearest color
+    /// (the nearest palette entry, if the button has a [`palette`](ColorEditButton::palette)).
+    Rich,
+}
+
+impl Default for ButtonTooltip {
+    fn default() -> Self {
+        ButtonTooltip::Rich
+    }
+}
+
+/// Adds the hover tooltip of a color button showing `color`. The color is only converted if
+/// the tooltip actually shows.
+pub(crate) fn button_tooltip(
+    response: Response,
+    tooltip: ButtonTooltip,
+    color: impl FnOnce() -> OklchA,
+    palette: Option<&Palette>,
+    style: SwatchStyle,
+) -> Response {
+    match tooltip {
+        ButtonTooltip::None => response,
+        ButtonTooltip::Text => response.on_hover_text("Click to edit color"),
+        ButtonTooltip::Rich => response.on_hover_ui(|ui| {
+            let color = color();
+            let col_srgba: Srgba = color.convert();
+            let swatch_size = ui.spacing().interact_size * 3.0;
+            ui.horizontal(|ui| {
+                show_color(ui, col_srgba.into_cint().into(), swatch_size, style);
+                ui.vertical(|ui| {
+                    ui.monospace(convert::to_hex(color));
+                    ui.monospace(convert::to_css_oklch(color));
+                    let name = palette
+                        .and_then(|palette| palette.nearest(color).map(|i| palette.entries[i].name.as_str()))
+                        .unwrap_or_else(|| names::nearest_color_name(color));
+                    ui.label(format!("≈ {}", name));
+                });
+            });
+            ui.label("Click to edit color");
+        }),
+    }
+}
+
+/// Returns the id of the toggle, the first widget of the popup.
+fn pin_toggle_ui(ui: &mut Ui, pinned: &mut bool) -> Id {
+    ui.with_layout(Layout::right_to_left(), |ui| {
+        let hover = if *pinned { "Unpin" } else { "Keep open" };
+        let response = ui.selectable_label(*pinned, "📌").on_hover_text(hover);
+        if response.clicked() {
+            *pinned = !*pinned;
+        }
+        response.id
+    })
+    .inner
+}
+
+/// Whether the picker with this popup id is open, as a popup or a pinned window.
+pub(crate) fn is_picker_shown(ui: &Ui, popup_id: Id) -> bool {
+    compat::is_popup_open(ui.ctx(), popup_id)
+        || temp_get!(ui, popup_id.with("pinned"), bool).unwrap_or(false)
+}
+
+/// Opens the popup of the [`ColorEditButton`] with the given [`id`](ColorEditButton::id), e.g.
+/// from a keyboard shortcut or menu item. Any other popup is closed.
+pub fn open_color_picker(ctx: &CtxRef, id: Id) {
+    compat::open_popup(ctx, id);
+}
+
+/// Closes the popup of the [`ColorEditButton`] with the given [`id`](ColorEditButton::id), if open.
+pub fn close_color_picker(ctx: &CtxRef, id: Id) {
+    if compat::is_popup_open(ctx, id) {
+        compat::close_popup(ctx);
+    }
+}
+
+/// Which coordinates the picker popup edits the color in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PickerMode {
+    /// Oklch hue, chroma and lightness, with a chroma × lightness plane.
+    Oklch,
+    /// Rectangular Oklab, with an a×b plane at fixed lightness.
+    Oklab,
+    /// CIE L\*a\*b\*, for matching against print/Lab specifications.
+    CieLab(WhitePoint),
+    /// CIE LCh(ab), the polar form of [`PickerMode::CieLab`].
+    CieLch(WhitePoint),
+    /// HSLuv, where saturation is relative to the sRGB gamut boundary at each hue and lightness.
+    Hsluv,
+    /// Correlated color temperature (1000K–12000K) and green–magenta tint, for lighting.
+    Temperature,
+}
+
+impl Default for PickerMode {
+    fn default() -> Self {
+        PickerMode::Oklch
+    }
+}
+
+/// The (cloneable) settings of a [`ColorEditButton`] that the picker UI needs.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PickerOptions {
+    pub mode: PickerMode,
+    pub show_cmyk: bool,
+    pub show_theme_preview: bool,
+    pub show_context_preview: bool,
+    pub show_surround_preview: bool,
+    /// Shown as rows of swatches in the harmonies panel; empty to hide it.
+    pub harmonies: Vec<Scheme>,
+    pub show_quick_adjust: bool,
+    /// How many committed colors the history strip keeps; 0 to hide it.
+    pub history_len: usize,
+    /// Where the history of the popup being shown is kept, set when showing it.
+    pub history_id: Option<Id>,
+    /// Where the swatch hovered in the popup being shown is kept, set when showing it.
+    pub hover_preview_id: Option<Id>,
+    pub show_diagnostics: bool,
+    pub show_contrast: bool,
+    pub show_gradient_preview: bool,
+    pub show_mixer: bool,
+    pub show_compositing: bool,
+    pub blend_backgrounds: Vec<Color32>,
+    pub snap: Snap,
+    /// `None` to follow whether the user is currently using a touch screen.
+    pub touch: Option<bool>,
+    /// Modifiers for fine adjustment while dragging; `None` for Shift.
+    pub fine_adjust: Option<Modifiers>,
+    /// Modifiers showing the loupe over the 2D areas; `None` for no loupe.
+    pub loupe: Option<Modifiers>,
+    pub marker: MarkerStyle,
+    pub show_gamut_boundary: bool,
+    /// Chroma of the hue slider's gradient; `None` for the picked color's own.
+    pub hue_preview_chroma: Option<f32>,
+    pub show_perceptual_grid: bool,
+    pub hue_unit: HueUnit,
+    pub controls: Controls,
+    pub constraint: Option<ColorConstraint>,
+    pub validator: Option<ColorValidator>,
+    /// Undo completed changes to a color failing `validator`.
+    pub block_invalid: bool,
+    pub token: Option<token::TokenBinding>,
+    pub number_format: NumberFormat,
+    pub show_lightness_gamut: bool,
+    pub snap_lightness_to_gamut: bool,
+    pub clamp_to_gamut: bool,
+    pub read_only: bool,
+    pub swatch: SwatchStyle,
+    pub alpha_preview: AlphaPreview,
+    pub layout: PopupLayout,
+    /// `None` to follow [`set_render_quality`](crate::set_render_quality).
+    pub render_quality: Option<RenderQuality>,
+}
+
+impl PickerOptions {
+    pub fn is_touch(&self, ui: &Ui) -> bool {
+        self.touch.unwrap_or_else(|| ui.input().any_touches())
+    }
+
+    /// The color the hue slider shows at hue `h` for the picked color `color`.
+    pub fn hue_preview(&self, color: OklchA, h: f32) -> OklchA {
+        let mut col = color;
+        col.col.h = h;
+        if let Some(c) = self.hue_preview_chroma {
+            // Limited to the gamut at each hue, as clipping would shift the hue shown.
+            col.col.c = c.min(convert::max_chroma_for(col.col.l, h));
+        }
+        col
+    }
+
+    pub fn render_quality(&self, ui: &Ui) -> RenderQuality {
+        self.render_quality.unwrap_or_else(|| render_quality(ui.ctx()))
+    }
+
+    /// Whether all of the fine-adjust modifiers are held. An empty set disables fine adjustment.
+    pub fn fine_adjust_held(&self, ui: &Ui) -> bool {
+        let required = self.fine_adjust.unwrap_or(Modifiers {
+            shift: true,
+            ..Default::default()
+        });
+        modifiers_held(required, ui.input().modifiers)
+    }
+
+    /// Whether the loupe of the 2D areas should show, see [`ColorEditButton::loupe`].
+    pub fn loupe_held(&self, ui: &Ui) -> bool {
+        self.loupe.map_or(false, |required| modifiers_held(required, ui.input().modifiers))
+    }
+}
+
+/// Whether all of the (at least one) `required` modifiers are `held`.
+fn modifiers_held(required: Modifiers, held: Modifiers) -> bool {
+    let any_required = required.alt || required.ctrl || required.shift || required.mac_cmd || required.command;
+    any_required
+        && (!required.alt || held.alt)
+        && (!required.ctrl || held.ctrl)
+        && (!required.shift || held.shift)
+        && (!required.mac_cmd || held.mac_cmd)
+        && (!required.command || held.command)
+}
+
+/// The text color of the [`ColorEditButton::validate`] message.
+const INVALID_COLOR_TEXT: Color32 = Color32::from_rgb(255, 110, 90);
+
+/// The color in words, for screen readers: "Lightness 62 percent, chroma 0.14, hue 200 degrees".
+fn describe_color(color: &OklchA) -> String {
+    let mut description = format!(
+        "Lightness {:.0} percent, chroma {:.2}, hue {:.0} degrees",
+        color.col.l * 100.0,
+        color.col.c,
+        color.col.h.to_degrees().rem_euclid(360.0),
+    );
+    if color.alpha < 1.0 {
+        description += &format!(", opacity {:.0} percent", color.alpha * 100.0);
+    }
+    description
+}
+
+/// How a change of the color fits into the user's gesture, see [`track_gesture`].
+#[derive(Clone, Copy, Debug, Default)]
+struct Gesture {
+    drag_started: bool,
+    drag_released: bool,
+    /// The change is complete: immediately for keyboard edits and buttons (which act on release),
+    /// on release for edits made while the pointer is down. Pressing a slider or 2D area changes
+    /// the color on the press, so even a click there completes on the release.
+    committed: bool,
+}
+
+/// Tells drags apart from one-off changes, using whether the pointer is held down. `changed`
+/// is whether the color changed this frame; the in-progress drag is kept in temp memory. A drag
+/// ends when the pointer is released or the picker stops being `shown`.
+fn track_gesture(ui: &Ui, id: Id, changed: bool, shown: bool) -> Gesture {
+    let dragging = temp_get!(ui, id, bool).unwrap_or(false);
+    let down = ui.input().pointer.any_down();
+    let mut gesture = Gesture::default();
+    if dragging && (!down || !shown) {
+        gesture.drag_released = true;
+        gesture.committed = true;
+        temp_remove!(ui, id, bool);
+    } else if changed && down && shown && !dragging {
+        gesture.drag_started = true;
+        temp_insert!(ui, id, true);
+    } else if changed && (!down || !shown) {
+        gesture.committed = true;
+    }
+    gesture
+}
+
+/// With [`ColorEditButton::block_invalid`], undoes a completed `gesture` ending on a color failing
+/// validation: back to `before` (the color before this frame's changes), or for a drag to the
+/// color before the drag, kept in temp memory under `id`. Returns whether it did.
+fn block_invalid_commit(
+    ui: &Ui,
+    id: Id,
+    options: &PickerOptions,
+    color: &mut OklchA,
+    before: OklchA,
+    gesture: &mut Gesture,
+) -> bool {
+    if !options.block_invalid {
+        return false;
+    }
+    if gesture.drag_started {
+        temp_insert!(ui, id, before);
+    }
+    if !gesture.committed {
+        return false;
+    }
+    let start = if gesture.drag_released {
+        let start = temp_get!(ui, id, OklchA);
+        temp_remove!(ui, id, OklchA);
+        start.unwrap_or(before)
+    } else {
+        before
+    };
+    let invalid = options.validator.as_ref().map_or(false, |validator| validator.validate(color).is_err());
+    if !invalid {
+        return false;
+    }
+    *color = start;
+    gesture.committed = false;
+    true
+}
+
+/// Emits a [`OutputEvent::ValueChanged`] describing `color`, for screen readers.
+fn announce_color(ui: &Ui, color: &OklchA) {
+    let info = WidgetInfo::labeled(WidgetType::ColorButton, describe_color(color));
+    ui.output().events.push(OutputEvent::ValueChanged(info));
+}
+
+/// The part of the picker popup that changed the color, see [`PickerResponse::changed_control`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PickerControl {
+    /// The sliders, 2D area or text fields of the picker itself.
+    Picker,
+    Cmyk,
+    Palette,
+    Adjust,
+    /// A color restored from the [`history`](ColorEditButton::history) strip.
+    History,
+    /// "Revert to token" of a [`token_color_edit`](crate::token_color_edit) button.
+    Token,
+    /// A swatch of the [`harmonies`](ColorEditButton::harmonies) panel.
+    Harmony,
+    /// The button's [`double_click`](ColorEditButton::double_click) action.
+    DoubleClick,
+    /// "Use mix" of the [`mixer`](ColorEditButton::mixer).
+    Mixer,
+    /// "Use overlay" of the [`compositing`](ColorEditButton::compositing) calculator.
+    Compositing,
+}
+
+/// What happened to a [`ColorEditButton`] this frame.
+pub struct PickerResponse {
+    /// The response of the color button, marked as changed whenever the color changed.
+    pub response: Response,
+    popup_open: bool,
+    changed_control: Option<PickerControl>,
+    gesture: Gesture,
+    committed_color: Option<OklchA>,
+}
+
+impl PickerResponse {
+    /// Whether the color changed this frame, including every frame of a drag.
+    pub fn changed(&self) -> bool {
+        self.response.changed()
+    }
+
+    /// A drag that changes the color started this frame, e.g. to snapshot the color for undo.
+    /// Pressing a slider counts, as it changes the color with the pointer down.
+    pub fn drag_started(&self) -> bool {
+        self.gesture.drag_started
+    }
+
+    /// A drag that changed the color ended this frame.
+    pub fn drag_released(&self) -> bool {
+        self.gesture.drag_released
+    }
+
+    /// The color, if a change was completed this frame: on the pointer's release for sliders and
+    /// 2D areas (also when just clicked), or right away for buttons and typed values. Use this
+    /// rather than [`changed`](Self::changed) to record history.
+    pub fn committed_color(&self) -> Option<OklchA> {
+        self.committed_color
+    }
+
+    /// Whether the popup (or pinned window) is showing.
+    pub fn popup_open(&self) -> bool {
+        self.popup_open
+    }
+
+    /// Which part of the popup changed the color this frame, if any.
+    pub fn changed_control(&self) -> Option<PickerControl> {
+        self.changed_control
+    }
+}
+
+/// A color button which opens a full color picker when clicked.
+///
+/// ```ignore
+/// ColorEditButton::new(&mut color)
+///     .mode(PickerMode::CieLch(WhitePoint::D50))
+///     .show(ui);
+/// ```
+pub struct ColorEditButton<'a> {
+    color: &'a mut OklchA,
+    options: PickerOptions,
+    id: Option<Id>,
+    id_salt: Option<Id>,
+    open: bool,
+    tooltip: ButtonTooltip,
+    double_click: DoubleClickAction,
+    palette: Option<&'a mut Palette>,
+    indexed: Option<(&'a Palette, &'a mut Option<usize>)>,
+    image: Option<&'a [Color32]>,
+    on_palette_load: Option<Box<dyn FnMut(&mut Palette) + 'a>>,
+    on_palette_save: Option<Box<dyn FnMut(&Palette) + 'a>>,
+    context_preview: Option<Box<dyn FnMut(&mut Ui, OklchA) + 'a>>,
+}
+
+impl<'a> ColorEditButton<'a> {
+    pub fn new(color: &'a mut OklchA) -> Self {
+        Self {
+            color,
+            options: PickerOptions::default(),
+            id: None,
+            id_salt: None,
+            open: false,
+            tooltip: ButtonTooltip::default(),
+            double_click: DoubleClickAction::default(),
+            palette: None,
+            indexed: None,
+            image: None,
+            on_palette_load: None,
+            on_palette_save: None,
+            context_preview: None,
+        }
+    }
+
+    /// An explicit id for the popup, so it can be opened with [`open_color_picker`]. Defaults to
+    /// an id derived from the button's own [`Response::id`].
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Derive the popup id from `salt` and the parent [`Ui`]'s id instead of the button's
+    /// position, e.g. to keep the popup open while the buttons around it come and go.
+    pub fn id_salt(mut self, salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(salt));
+        self
+    }
+
+    /// Opens the popup this frame if `open` is true, as if the button had been clicked.
+    /// Use it with the result of a shortcut check: `.open(ui.input().key_pressed(Key::C))`.
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    /// How the button and the selected color preview show alpha. Defaults to
+    /// [`AlphaDisplay::SplitHalves`].
+    pub fn alpha_display(mut self, display: AlphaDisplay) -> Self {
+        self.options.swatch.alpha = display;
+        self
+    }
+
+    /// How the alpha slider previews transparency, until the user switches it with the toggle
+    /// beside it. Defaults to [`AlphaPreview::Premultiplied`].
+    pub fn alpha_preview(mut self, preview: AlphaPreview) -> Self {
+        self.options.alpha_preview = preview;
+        self
+    }
+
+    /// What to draw behind transparent colors in the button, the previews and the alpha
+    /// slider. Defaults to gray checkers.
+    pub fn backdrop(mut self, backdrop: Backdrop) -> Self {
+        self.options.swatch.backdrop = backdrop;
+        self
+    }
+
+    /// What hovering the button shows. Defaults to [`ButtonTooltip::Rich`].
+    pub fn tooltip(mut self, tooltip: ButtonTooltip) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+
+    /// What double-clicking the button does. Defaults to [`DoubleClickAction::None`].
+    pub fn double_click(mut self, action: DoubleClickAction) -> Self {
+        self.double_click = action;
+        self
+    }
+
+    /// Show the color and its values without allowing changes, for viewers. The popup still
+    /// opens, with all of its controls disabled.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.options.read_only = read_only;
+        self
+    }
+
+    /// Which coordinates to edit the color in. Defaults to [`PickerMode::Oklch`].
+    pub fn mode(mut self, mode: PickerMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
+    /// Show a naive CMYK readout and sliders below the picker, for a ballpark print view.
+    pub fn cmyk(mut self, show: bool) -> Self {
+        self.options.show_cmyk = show;
+        self
+    }
+
+    /// Show a bar with the Oklab gradient from the color to a second, pinned color, e.g. to
+    /// try out endpoint pairs for a chart or heatmap.
+    pub fn gradient_preview(mut self, show: bool) -> Self {
+        self.options.show_gradient_preview = show;
+        self
+    }
+
+    /// Show a mixer blending the color with a second one (white, black, gray or a pinned color)
+    /// in Oklab or Oklch, with a button making the mix the color, e.g. to add 30% of white.
+    pub fn mixer(mut self, show: bool) -> Self {
+        self.options.show_mixer = show;
+        self
+    }
+
+    /// Show a compositing calculator: the color composited over a background, and the most
+    /// transparent (or a fixed-alpha) overlay that composites over it to the color. See
+    /// [`composite_over`](crate::composite_over) and [`uncomposite`](crate::uncomposite).
+    pub fn compositing(mut self, show: bool) -> Self {
+        self.options.show_compositing = show;
+        self
+    }
+
+    /// Show the color composited over each of `backgrounds` (e.g. the light and dark theme's
+    /// panel colors), to see what a translucent color really looks like where it is used.
+    pub fn blend_preview(mut self, backgrounds: impl IntoIterator<Item = Color32>) -> Self {
+        self.options.blend_backgrounds = backgrounds.into_iter().collect();
+        self
+    }
+
+    /// Show the WCAG 2 contrast ratio and APCA Lc of the color as text on, and as a background
+    /// for, white and black (or the [`blend_preview`](Self::blend_preview) backgrounds).
+    pub fn contrast(mut self, show: bool) -> Self {
+        self.options.show_contrast = show;
+        self
+    }
+
+    /// Show the derived quantities rendering and accessibility work needs: CIE luminance Y,
+    /// WCAG relative luminance and Oklab lightness, each with a copy button.
+    pub fn diagnostics(mut self, show: bool) -> Self {
+        self.options.show_diagnostics = show;
+        self
+    }
+
+    /// Show a strip of the last `len` committed colors of this session below the picker. Hovering
+    /// one previews it next to the current color, clicking restores it.
+    pub fn history(mut self, len: usize) -> Self {
+        self.options.history_len = len;
+        self
+    }
+
+    /// Show buttons below the picker that lighten, darken, saturate or desaturate the color by a
+    /// small fixed step, keeping its hue. The same adjustments are available as
+    /// [`convert::lighten`] and friends.
+    pub fn quick_adjust(mut self, show: bool) -> Self {
+        self.options.show_quick_adjust = show;
+        self
+    }
+
+    /// Show a preview of the egui theme [`theme::visuals_from_seed`](crate::theme::visuals_from_seed)
+    /// derives from the color.
+    pub fn theme_preview(mut self, show: bool) -> Self {
+        self.options.show_theme_preview = show;
+        self
+    }
+
+    /// Show the colors of `schemes` for the current color, picking a color when clicked.
+    /// [`Scheme::presets`] are the usual ones.
+    pub fn harmonies(mut self, schemes: impl IntoIterator<Item = Scheme>) -> Self {
+        self.options.harmonies = schemes.into_iter().collect();
+        self
+    }
+
+    /// Show the color in context: as a button fill, as text on light and dark backgrounds, and as
+    /// a thin stroke. See [`Self::context_preview_ui`] for a custom preview.
+    pub fn context_preview(mut self, show: bool) -> Self {
+        self.options.show_context_preview = show;
+        self
+    }
+
+    /// Show the swatch of the color in a light and a dark surround side by side, since the
+    /// perceived lightness and chroma of a color shift with what surrounds it.
+    pub fn surround_preview(mut self, show: bool) -> Self {
+        self.options.show_surround_preview = show;
+        self
+    }
+
+    /// Show the color in context with `preview`, called with the candidate color, instead of
+    /// the built-in preview of [`Self::context_preview`].
+    pub fn context_preview_ui(mut self, preview: impl FnMut(&mut Ui, OklchA) + 'a) -> Self {
+        self.options.show_context_preview = true;
+        self.context_preview = Some(Box::new(preview));
+        self
+    }
+
+    /// The width of the popup's contents. Defaults to what the widgets need.
+    pub fn popup_width(mut self, width: f32) -> Self {
+        self.options.layout.width = Some(width);
+        self
+    }
+
+    /// The width of the sliders in the popup, instead of [`style::Spacing::slider_width`].
+    pub fn slider_width(mut self, width: f32) -> Self {
+        self.options.layout.slider_width = Some(width);
+        self
+    }
+
+    /// The side of the square 2D areas in the popup. Defaults to the slider width.
+    pub fn area_size(mut self, size: f32) -> Self {
+        self.options.layout.area_size = Some(size);
+        self
+    }
+
+    /// Show a magnified, resampled patch of the 2D area around the pointer while all of
+    /// `modifiers` are held, e.g. to tell apart subtle chroma differences near grays.
+    pub fn loupe(mut self, modifiers: Modifiers) -> Self {
+        self.options.loupe = Some(modifiers);
+        self
+    }
+
+    /// How finely to tessellate the sliders, instead of the context-wide
+    /// [`set_render_quality`](crate::set_render_quality).
+    pub fn render_quality(mut self, quality: RenderQuality) -> Self {
+        self.options.render_quality = Some(quality);
+        self
+    }
+
+    /// Force the touch-friendly layout (taller sliders, relative dragging in the 2D area) on or
+    /// off. By default it is used whenever the user is touching the screen.
+    pub fn touch(mut self, touch: bool) -> Self {
+        self.options.touch = Some(touch);
+        self
+    }
+
+    /// Modifiers that, held while dragging a slider, scale pointer movement by 0.1× for precise
+    /// adjustments. Shift by default; pass `Modifiers::default()` to disable.
+    pub fn fine_adjust_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.options.fine_adjust = Some(modifiers);
+        self
+    }
+
+    /// Change how the markers on the sliders are drawn.
+    pub fn marker_style(mut self, marker: MarkerStyle) -> Self {
+        self.options.marker = marker;
+        self
+    }
+
+    /// Draw the sRGB gamut boundary at the current hue over the chroma × lightness area
+    /// (Oklch mode), showing how far chroma can go at each lightness.
+    pub fn gamut_boundary(mut self, show: bool) -> Self {
+        self.options.show_gamut_boundary = show;
+        self
+    }
+
+    /// Overlay a grid of roughly constant perceptual spacing on the 2D area: lines of constant
+    /// lightness and chroma in Oklch mode, circles of constant chroma and rays of constant hue
+    /// in Oklab mode. Handy for building evenly spaced palettes by eye.
+    pub fn perceptual_grid(mut self, show: bool) -> Self {
+        self.options.show_perceptual_grid = show;
+        self
+    }
+
+    /// Draw the hue slider at chroma `chroma` (as far as the gamut allows) and the current
+    /// lightness, rather than at the current chroma, so it still shows the hues when the color
+    /// is gray. Only the slider changes; picking a hue sets just the hue of the color.
+    pub fn hue_preview_chroma(mut self, chroma: f32) -> Self {
+        self.options.hue_preview_chroma = Some(chroma);
+        self
+    }
+
+    /// Show and enter hues in `unit`, in the readouts and the hue field (Oklch mode). Colors
+    /// store hue in radians regardless. Defaults to degrees.
+    pub fn hue_unit(mut self, unit: HueUnit) -> Self {
+        self.options.hue_unit = unit;
+        self
+    }
+
+    pub(crate) fn token(mut self, token: token::TokenBinding) -> Self {
+        self.options.token = Some(token);
+        self
+    }
+
+    /// Only allow picking the colors `constraint` allows, see [`ColorConstraint`].
+    pub fn constraint(mut self, constraint: ColorConstraint) -> Self {
+        self.options.constraint = Some(constraint);
+        self
+    }
+
+    /// Check the picked color with `validator`, showing its message in the popup while the
+    /// color fails. See [`block_invalid`](Self::block_invalid) to also refuse such colors.
+    pub fn validate(mut self, validator: ColorValidator) -> Self {
+        self.options.validator = Some(validator);
+        self
+    }
+
+    /// Refuse to commit colors failing the [`validate`](Self::validate) check: a change ending
+    /// on one (the release of a drag, or a click) goes back to the color before it, and
+    /// [`PickerResponse::committed_color`] stays `None`.
+    pub fn block_invalid(mut self, block: bool) -> Self {
+        self.options.block_invalid = block;
+        self
+    }
+
+    /// Show only some of the picker's controls, see [`Controls`].
+    pub fn controls(mut self, controls: Controls) -> Self {
+        self.options.controls = controls;
+        self
+    }
+
+    /// How numbers are shown in the readouts, number fields and copied text, see
+    /// [`NumberFormat`].
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.options.number_format = format;
+        self
+    }
+
+    /// Mark on the lightness slider where the color at the current chroma and hue leaves the
+    /// sRGB gamut (Oklch mode). Past the marks the slider shows clipped colors.
+    pub fn lightness_gamut_marks(mut self, show: bool) -> Self {
+        self.options.show_lightness_gamut = show;
+        self
+    }
+
+    /// Keep the lightness slider between those marks, see [`Self::lightness_gamut_marks`].
+    pub fn snap_lightness_to_gamut(mut self, snap: bool) -> Self {
+        self.options.snap_lightness_to_gamut = snap;
+        self
+    }
+
+    /// Keep picked colors inside the sRGB gamut by limiting their chroma (Oklch mode).
+    pub fn clamp_to_gamut(mut self, clamp: bool) -> Self {
+        self.options.clamp_to_gamut = clamp;
+        self
+    }
+
+    /// Snap picked colors to discrete steps, see [`Snap`].
+    pub fn snap(mut self, snap: Snap) -> Self {
+        self.options.snap = snap;
+        self
+    }
+
+    /// Show a palette panel below the picker: clicking a swatch picks its color, and the
+    /// current color can be added to the palette.
+    pub fn palette(mut self, palette: &'a mut Palette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Restrict the picker to the entries of `palette` (e.g. for pixel art). The sliders and
+    /// 2D area show the nearest entry at each position, and `index` receives the index of the
+    /// picked entry alongside the color itself.
+    pub fn indexed(mut self, palette: &'a Palette, index: &'a mut Option<usize>) -> Self {
+        self.indexed = Some((palette, index));
+        self
+    }
+
+    /// Offers to fill the attached [`palette`](Self::palette) with the dominant colors of an
+    /// image (see [`extract_palette`](crate::extract_palette)). egui textures can't be read back, so pass the decoded
+    /// pixels, e.g. those of an image the user just dropped onto the window.
+    pub fn image_source(mut self, pixels: &'a [Color32]) -> Self {
+        self.image = Some(pixels);
+        self
+    }
+
+    /// Adds a "Load…" button to the palette panel which calls `load`, e.g. to show a file
+    /// dialog and read the result with [`Palette::from_gpl`].
+    pub fn on_palette_load(mut self, load: impl FnMut(&mut Palette) + 'a) -> Self {
+        self.on_palette_load = Some(Box::new(load));
+        self
+    }
+
+    /// Adds a "Save…" button to the palette panel which calls `save`.
+    pub fn on_palette_save(mut self, save: impl FnMut(&Palette) + 'a) -> Self {
+        self.on_palette_save = Some(Box::new(save));
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> PickerResponse {
+        let col_srgba: Srgba = self.color.convert();
+        let button_response = color_button(ui, col_srgba.into_cint().into(), self.options.swatch);
+        self.show_impl(ui, button_response, col_srgba)
+    }
+
+    /// The popup id: the explicit [`id`](Self::id), else from the [`id_salt`](Self::id_salt),
+    /// else derived from the button's own id.
+    fn popup_id(&self, ui: &Ui, button_response: &Response) -> Id {
+        match (self.id, self.id_salt) {
+            (Some(id), _) => id,
+            (None, Some(salt)) => ui.make_persistent_id(salt),
+            (None, None) => button_response.id.with("popup"),
+        }
+    }
+
+    /// [`show`](Self::show) for a color button that has already been drawn.
+    pub(crate) fn show_with_button(self, ui: &mut Ui, button_response: Response) -> PickerResponse {
+        let col_srgba: Srgba = self.color.convert();
+        self.show_impl(ui, button_response, col_srgba)
+    }
+
+    /// [`show_with_button`](Self::show_with_button) with the color as `col_srgba` already.
+    fn show_impl(mut self, ui: &mut Ui, button_response: Response, col_srgba: Srgba) -> PickerResponse {
+        let popup_id = self.popup_id(ui, &button_response);
+        if self.open && !compat::is_popup_open(ui.ctx(), popup_id) {
+            compat::open_popup(ui.ctx(), popup_id);
+        }
+        let was_shown = is_picker_shown(ui, popup_id);
+
+        let mut changed_control = None;
+        let before = *self.color;
+        let style = self.options.swatch;
+        let layout = self.options.layout;
+        self.options.history_id = Some(popup_id.with("history"));
+        self.options.hover_preview_id = Some(popup_id.with("hover_preview"));
+        let double_click_toggles = self.double_click == DoubleClickAction::None;
+        let mut response = color_edit_popup(ui, button_response, popup_id, layout, double_click_toggles, |ui| {
+            changed_control = self.picker_ui(ui, col_srgba);
+            changed_control.is_some()
+        });
+        // With an action, the second click runs it instead of closing the popup the first opened.
+        if response.double_clicked() {
+            match self.double_click {
+                DoubleClickAction::None => {}
+                DoubleClickAction::Reset(reset) => {
+                    if reset != *self.color && !self.options.read_only {
+                        *self.color = reset;
+                        changed_control = Some(PickerControl::DoubleClick);
+                        response.mark_changed();
+                    }
+                }
+                DoubleClickAction::CopyHex => ui.output().copied_text = convert::to_hex(*self.color),
+            }
+        }
+        let color = *self.color;
+        let mut response = button_tooltip(response, self.tooltip, || color, self.palette.as_deref(), style);
+
+        // Closed buttons that didn't change skip the bookkeeping; a drag still going on when the
+        // popup closes is complete as of that frame.
+        let popup_open = is_picker_shown(ui, popup_id);
+        let mut gesture = Gesture::default();
+        if was_shown || popup_open || changed_control.is_some() {
+            gesture = track_gesture(ui, popup_id.with("gesture"), changed_control.is_some(), popup_open);
+            let start_id = popup_id.with("gesture_start");
+            if block_invalid_commit(ui, start_id, &self.options, self.color, before, &mut gesture) {
+                response.mark_changed();
+            }
+        }
+        if gesture.committed {
+            announce_color(ui, self.color);
+            record_history(ui.ctx(), &self.options, *self.color);
+        }
+        PickerResponse {
+            popup_open,
+            response,
+            changed_control,
+            gesture,
+            committed_color: if gesture.committed { Some(*self.color) } else { None },
+        }
+    }
+
+    /// The contents of the popup. Returns what changed the color, if anything.
+    fn picker_ui(&mut self, ui: &mut Ui, col_srgba: Srgba) -> Option<PickerControl> {
+        if !self.options.read_only {
+            return self.edit_ui(ui, col_srgba);
+        }
+        // Everything is still shown, but greyed out, and nothing the widgets do sticks.
+        let orig_col = *self.color;
+        ui.scope(|ui| {
+            ui.set_enabled(false);
+            self.edit_ui(ui, col_srgba);
+        });
+        *self.color = orig_col;
+        None
+    }
+
+    fn edit_ui(&mut self, ui: &mut Ui, col_srgba: Srgba) -> Option<PickerControl> {
+        let tab_id = ui.make_persistent_id("popup_tab");
+        let mut tab = temp_get!(ui, tab_id, PopupTab).unwrap_or(PopupTab::Pick);
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut tab, PopupTab::Pick, "Pick");
+            ui.selectable_value(&mut tab, PopupTab::Adjust, "Adjust");
+        });
+        temp_insert!(ui, tab_id, tab);
+        ui.separator();
+
+        let changed = match tab {
+            PopupTab::Pick => self.pick_tab_ui(ui, col_srgba),
+            PopupTab::Adjust => {
+                if adjust_ui(ui, self.color, &self.options) {
+                    Some(PickerControl::Adjust)
+                } else {
+                    None
+                }
+            }
+        };
+
+        // Whatever changed the color, an indexed picker may only output palette entries.
+        if let Some((palette, index)) = &mut self.indexed {
+            if changed.is_some() {
+                if let Some(i) = palette.nearest(*self.color) {
+                    **index = Some(i);
+                    *self.color = palette.entries[i].color;
+                }
+            }
+        }
+
+        if let Some(validator) = &self.options.validator {
+            if let Err(message) = validator.validate(self.color) {
+                ui.separator();
+                ui.add(Label::new(format!("⚠ {}", message)).text_color(INVALID_COLOR_TEXT));
+            }
+        }
+        changed
+    }
+
+    fn pick_tab_ui(&mut self, ui: &mut Ui, col_srgba: Srgba) -> Option<PickerControl> {
+        let color = &mut *self.color;
+        let orig_color = *color;
+        let options = &self.options;
+        let mut changed = None;
+        if let Some(token) = &options.token {
+            if token::token_ui(ui, color, token, options.swatch) {
+                changed = Some(PickerControl::Token);
+            }
+        }
+        // A swatch hovered in the last frame is shown in the picker, without changing the color.
+        let preview = options
+            .hover_preview_id
+            .and_then(|id| temp_get!(ui, id, OklchA))
+            .filter(|preview| *preview != *color);
+        let mut hovered = None;
+        let picked = match (&mut self.indexed, preview) {
+            (Some((palette, index)), _) => color_picker_indexed(ui, color, palette, index, options),
+            (None, Some(mut preview)) => {
+                let preview_srgba = preview.convert();
+                mode_picker_ui(ui, &mut preview, preview_srgba, options);
+                false
+            }
+            (None, None) => mode_picker_ui(ui, color, col_srgba, options),
+        };
+        if picked {
+            changed = Some(PickerControl::Picker);
+        }
+        if options.show_quick_adjust && quick_adjust_ui(ui, color, options) {
+            changed = Some(PickerControl::Adjust);
+        }
+        if options.history_len > 0 && history_ui(ui, color, options, &mut hovered) {
+            changed = Some(PickerControl::History);
+        }
+        if !options.harmonies.is_empty()
+            && CollapsingHeader::new("Harmonies")
+                .default_open(true)
+                .show(ui, |ui| harmonies_ui(ui, color, options, &mut hovered))
+                .body_returned
+                .unwrap_or(false)
+        {
+            changed = Some(PickerControl::Harmony);
+        }
+        if options.show_cmyk {
+            let cmyk_changed = CollapsingHeader::new("CMYK")
+                .default_open(true)
+                .show(ui, |ui| cmyk_ui(ui, color, options))
+                .body_returned
+                .unwrap_or(false);
+            if cmyk_changed {
+                changed = Some(PickerControl::Cmyk);
+            }
+        }
+        if options.show_gradient_preview
+            && CollapsingHeader::new("Gradient to…")
+                .default_open(true)
+                .show(ui, |ui| gradient_pair_ui(ui, color, options))
+                .body_returned
+                .unwrap_or(false)
+        {
+            changed = Some(PickerControl::Picker);
+        }
+        if options.show_mixer
+            && CollapsingHeader::new("Mix")
+                .default_open(true)
+                .show(ui, |ui| mixer_ui(ui, color, options))
+                .body_returned
+                .unwrap_or(false)
+        {
+            changed = Some(PickerControl::Mixer);
+        }
+        if options.show_compositing
+            && CollapsingHeader::new("Compositing")
+                .default_open(true)
+                .show(ui, |ui| compositing_ui(ui, color, options))
+                .body_returned
+                .unwrap_or(false)
+        {
+            changed = Some(PickerControl::Compositing);
+        }
+        if !options.blend_backgrounds.is_empty() {
+            blend_preview_ui(ui, *color, &options.blend_backgrounds);
+        }
+        if options.show_contrast {
+            CollapsingHeader::new("Contrast")
+                .default_open(true)
+                .show(ui, |ui| contrast_ui(ui, *color, &options.blend_backgrounds));
+        }
+        if options.show_diagnostics {
+            CollapsingHeader::new("Diagnostics")
+                .default_open(true)
+                .show(ui, |ui| diagnostics_ui(ui, *color, &options.number_format));
+        }
+        if options.show_theme_preview {
+            CollapsingHeader::new("Theme preview")
+                .default_open(true)
+                .show(ui, |ui| theme_preview_ui(ui, *color));
+        }
+        if options.show_context_preview {
+            let custom = self.context_preview.as_deref_mut();
+            CollapsingHeader::new("In context").default_open(true).show(ui, |ui| match custom {
+                Some(preview) => preview(ui, *color),
+                None => context_preview_ui(ui, *color),
+            });
+        }
+        if options.show_surround_preview {
+            CollapsingHeader::new("Surround")
+                .default_open(true)
+                .show(ui, |ui| surround_preview_ui(ui, *color, options.swatch));
+        }
+        if let Some(palette) = self.palette.as_deref_mut() {
+            let mut palette_changed = nearest_palette_ui(ui, color, palette);
+            let on_load = self.on_palette_load.as_deref_mut();
+            let on_save = self.on_palette_save.as_deref_mut();
+            let image = self.image;
+            palette_changed |= CollapsingHeader::new("Palette")
+                .default_open(true)
+                .show(ui, |ui| palette_panel_ui(ui, color, palette, on_load, on_save, image, &mut hovered))
+                .body_returned
+                .unwrap_or(false);
+            if palette_changed {
+                changed = Some(PickerControl::Palette);
+            }
+        }
+        if let Some(id) = options.hover_preview_id {
+            match hovered {
+                Some(hovered) => temp_insert!(ui, id, hovered),
+                None => temp_remove!(ui, id, OklchA),
+            }
+        }
+        if let (Some(constraint), Some(_)) = (&options.constraint, changed) {
+            *color = constraint.limit(orig_color, *color);
+        }
+        changed
+    }
+}
+
+/// `ui.add(ColorEditButton::new(&mut color))`, or with `ui.add_enabled(..)` and friends.
+impl Widget for ColorEditButton<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// One picker window shared by many color buttons, e.g. the swatches of a palette editor.
+/// Clicking a button binds the window to it; only the bound color is edited.
+///
+/// ```ignore
+/// let shared = SharedPicker::new("palette_editor");
+/// for (i, color) in colors.iter_mut().enumerate() {
+///     shared.show(ui, i, ColorEditButton::new(color));
+/// }
+/// ```
+///
+/// The window is drawn by the bound button's [`show`](SharedPicker::show) call, so it only
+/// appears while that button is being shown.
+#[derive(Clone, Copy, Debug)]
+pub struct SharedPicker {
+    id: Id,
+}
+
+impl SharedPicker {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+        }
+    }
+
+    /// The id of the target the picker is bound to, as passed to [`show`](Self::show)
+    /// and hashed with [`Id::new`].
+    pub fn active(&self, ctx: &CtxRef) -> Option<Id> {
+        temp_get!(ctx, self.id, Id)
+    }
+
+    /// Binds the picker to `target` (see [`active`](Self::active)), or closes it with `None`.
+    pub fn set_active(&self, ctx: &CtxRef, target: Option<Id>) {
+        match target {
+            Some(target) => temp_insert!(ctx, self.id, target),
+            None => temp_remove!(ctx, self.id, Id),
+        }
+    }
+
+    /// Shows `button` as a plain color button for the color identified by `target`.
+    /// All options of `button` apply to the shared window while it is bound to this target.
+    pub fn show(&self, ui: &mut Ui, target: impl std::hash::Hash, mut button: ColorEditButton<'_>) -> PickerResponse {
+        let target = Id::new(target);
+        let is_active = self.active(ui.ctx()) == Some(target);
+        let col_srgba: Srgba = button.color.convert();
+
+        let color = *button.color;
+        let mut response = button_tooltip(
+            color_button(ui, col_srgba.into_cint().into(), button.options.swatch),
+            button.tooltip,
+            || color,
+            button.palette.as_deref(),
+            button.options.swatch,
+        );
+        if is_active {
+            let stroke = Stroke::new(2.0, ui.visuals().selection.stroke.color);
+            ui.painter().rect_stroke(response.rect.expand(1.0), 0.0, stroke);
+        }
+        if response.clicked() {
+            self.set_active(ui.ctx(), if is_active { None } else { Some(target) });
+        }
+
+        let mut changed_control = None;
+        let before = *button.color;
+        button.options.history_id = Some(self.id.with("history"));
+        button.options.hover_preview_id = Some(self.id.with("hover_preview"));
+        if self.active(ui.ctx()) == Some(target) {
+            let mut open = true;
+            Window::new("Color")
+                .id(self.id.with("window"))
+                .open(&mut open)
+                .resizable(false)
+                .default_pos(response.rect.max)
+                .show(ui.ctx(), |ui| {
+                    button.options.layout.apply(ui);
+                    changed_control = button.picker_ui(ui, col_srgba);
+                });
+            if !open {
+                self.set_active(ui.ctx(), None);
+            }
+        }
+        if changed_control.is_some() {
+            response.mark_changed();
+        }
+
+        // As in `ColorEditButton::show`, only for the bound target or a changed color.
+        let popup_open = self.active(ui.ctx()) == Some(target);
+        let mut gesture = Gesture::default();
+        if is_active || popup_open || changed_control.is_some() {
+            let gesture_id = self.id.with(target).with("gesture");
+            gesture = track_gesture(ui, gesture_id, changed_control.is_some(), popup_open);
+            let start_id = self.id.with(target).with("gesture_start");
+            if block_invalid_commit(ui, start_id, &button.options, button.color, before, &mut gesture) {
+                response.mark_changed();
+            }
+        }
+        if gesture.committed {
+            announce_color(ui, button.color);
+            record_history(ui.ctx(), &button.options, *button.color);
+        }
+        PickerResponse {
+            popup_open,
+            response,
+            changed_control,
+            gesture,
+            committed_color: if gesture.committed { Some(*button.color) } else { None },
+        }
+    }
+}
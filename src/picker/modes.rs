@@ -0,0 +1,719 @@
+//! The picker modes: the sliders for editing a color in each color space.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use super::{PickerMode, PickerOptions};
+use crate::sliders::{
+    color_slider_1d, color_slider_2d, color_slider_2d_cells, hue_drag_value, paint_delta_e_grid, paint_disallowed,
+    paint_gamut_boundary, paint_hue_chroma_grid, paint_lightness_gamut_ticks, paint_snap_ticks,
+    unit_interval_drag_value,
+};
+use crate::{
+    alpha_label_ui, alpha_slider, cielab, convert, custom_formats, hsluv, model, palette_grid, show_color, temperature,
+    GamutPolicy, LinearSrgbA, OklabA, OklchA, Palette, Srgba, WhitePoint,
+};
+
+fn color_text_ui(ui: &mut Ui, color: Srgba, options: &PickerOptions) {
+    if !options.controls.text {
+        return;
+    }
+    let format = &options.number_format;
+    ui.horizontal(|ui| {
+        let [r, g, b, a] = color.to_u8();
+        let [r, g, b, a] = [
+            format.srgb_channel(r),
+            format.srgb_channel(g),
+            format.srgb_channel(b),
+            format.srgb_channel(a),
+        ];
+        ui.label(format!(
+            "Encoded sRGB + Alpha (premultiplied): ({}, {}, {}, {})",
+            r, g, b, a
+        ));
+
+        if ui.button("📋").on_hover_text("Click to copy").clicked() {
+            ui.output().copied_text = format!("{}, {}, {}, {}", r, g, b, a);
+        }
+    });
+    ui.horizontal(|ui| {
+        let css = convert::to_css_oklch_in(color.convert(), options.hue_unit, format);
+        ui.label(&css);
+        if ui.button("📋").on_hover_text("Click to copy").clicked() {
+            ui.output().copied_text = css;
+        }
+    });
+    // Not a menu: its own area would count as a click outside the popup and close it.
+    CollapsingHeader::new("Copy as code").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            for (label, code) in code_snippets(color).iter() {
+                if ui.button(*label).on_hover_text(code).clicked() {
+                    ui.output().copied_text = code.clone();
+                }
+            }
+            let oklch: OklchA = color.convert();
+            for (name, format) in custom_formats(ui.ctx()).0.iter() {
+                let code = format(oklch);
+                if ui.button(name).on_hover_text(&code).clicked() {
+                    ui.output().copied_text = code;
+                }
+            }
+        });
+    });
+}
+
+/// Rust expressions constructing `color`, for hard-coding colors in code.
+fn code_snippets(color: Srgba) -> [(&'static str, String); 3] {
+    let [r, g, b, a] = color.to_u8();
+    let oklch: OklchA = color.convert();
+    let linear: LinearSrgbA = oklch.convert();
+    [
+        (
+            "Color32",
+            format!("Color32::from_rgba_premultiplied({}, {}, {}, {})", r, g, b, a),
+        ),
+        (
+            "Rgba",
+            format!(
+                "Rgba::from_rgba_unmultiplied({:.4}, {:.4}, {:.4}, {:.4})",
+                linear.col.r, linear.col.g, linear.col.b, linear.alpha
+            ),
+        ),
+        (
+            "colstodian Oklch",
+            format!(
+                "ColorAlpha::<Oklch, Separate>::new({:.4}, {:.4}, {:.4}, {:.4})",
+                oklch.col.l, oklch.col.c, oklch.col.h, oklch.alpha
+            ),
+        ),
+    ]
+}
+
+fn color_picker_oklch_2d(ui: &mut Ui, color: &mut OklchA, col_srgba: Srgba, options: &PickerOptions) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    color_text_ui(ui, col_srgba, options);
+
+    let grid_id = "oklab_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let mut opaque = *color;
+        opaque.alpha = 1.0;
+
+        if options.controls.alpha {
+            alpha_slider(ui, options, &mut color.alpha, |a| {
+                let mut col = opaque;
+                col.alpha = a;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            ui.horizontal(|ui| {
+                unit_interval_drag_value(ui, &options.number_format, &mut color.alpha);
+                alpha_label_ui(ui, options);
+            });
+            ui.end_row();
+        }
+
+        if options.controls.preview {
+            let selected: Color32 = color.convert_to::<Srgba>().saturate().into_cint().into();
+            show_color(ui, selected, current_color_size, options.swatch);
+            ui.label("Selected color");
+            ui.end_row();
+        }
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        let snap = &options.snap;
+        let ticks = |ui: &Ui, response: Response, range, step| {
+            if snap.show_grid {
+                paint_snap_ticks(ui, response.rect, range, step);
+            }
+        };
+
+        use core::f32::consts::PI;
+        if options.controls.hue {
+            let response = color_slider_1d(ui, options, &mut color.col.h, -PI..=PI, |h| {
+                let col = options.hue_preview(opaque, h);
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            ticks(ui, response, -PI..=PI, snap.hue);
+            ui.horizontal(|ui| {
+                hue_drag_value(ui, options.hue_unit, &options.number_format, &mut color.col.h);
+                ui.label("Hue");
+            });
+            ui.end_row();
+        }
+
+        if options.controls.chroma {
+            let response = color_slider_1d(ui, options, &mut color.col.c,0.0..=0.5, |c| {
+                let mut col = opaque;
+                col.col.c = c;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            ticks(ui, response, 0.0..=0.5, snap.chroma);
+            ui.horizontal(|ui| {
+                let decimals = options.number_format.decimals;
+                ui.add(DragValue::new(&mut color.col.c).speed(0.001).clamp_range(0.0..=0.5).fixed_decimals(decimals));
+                ui.label("Chroma");
+            });
+            ui.end_row();
+        }
+
+        if options.controls.lightness {
+            let orig_l = color.col.l;
+            let response = color_slider_1d(ui, options, &mut color.col.l, 0.0..=1.0, |l| {
+                let mut col = opaque;
+                col.col.l = l;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            if options.show_lightness_gamut || options.snap_lightness_to_gamut {
+                if let Some(range) = convert::in_gamut_lightness_range(opaque.col.c, opaque.col.h) {
+                    if options.show_lightness_gamut {
+                        paint_lightness_gamut_ticks(ui, response.rect, range.clone());
+                    }
+                    if options.snap_lightness_to_gamut && color.col.l != orig_l {
+                        color.col.l = color.col.l.clamp(*range.start(), *range.end());
+                    }
+                }
+            }
+            ticks(ui, response, 0.0..=1.0, snap.lightness);
+            ui.horizontal(|ui| {
+                unit_interval_drag_value(ui, &options.number_format, &mut color.col.l);
+                ui.label("Lightness");
+            });
+            ui.end_row();
+        }
+
+        if options.controls.area {
+            let col = &mut color.col;
+            let response = color_slider_2d(ui, options, &mut col.c, 0.0..=0.5, &mut col.l, 0.0..=1.0, |c, l| {
+                let mut col = opaque;
+                col.col.c = c;
+                col.col.l = l;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            if options.show_perceptual_grid {
+                paint_delta_e_grid(ui, response.rect, 0.0..=0.5, 0.0..=1.0);
+            }
+            if options.show_gamut_boundary {
+                let n = options.render_quality(ui).subdivisions_2d;
+                paint_gamut_boundary(ui, response.rect, opaque.col.h, 0.0..=0.5, n);
+            }
+            if let Some(constraint) = &options.constraint {
+                let n = options.render_quality(ui).subdivisions_2d;
+                paint_disallowed(ui, response.rect, 0.0..=0.5, 0.0..=1.0, n, |c, l| {
+                    constraint.allows(OklchA::new(l, c, opaque.col.h, color.alpha))
+                });
+            }
+            ui.label("Lightness / Chroma");
+            ui.end_row();
+        }
+    });
+
+    if *color == orig_col {
+        false
+    } else {
+        let gamut = if options.clamp_to_gamut {
+            GamutPolicy::ReduceChroma
+        } else {
+            GamutPolicy::Allow
+        };
+        *color = model::constrain(orig_col, *color, gamut, &options.snap, None);
+        true
+    }
+}
+
+pub(crate) fn color_picker_oklab_2d(
+    ui: &mut Ui,
+    color: &mut OklabA,
+    col_srgba: Srgba,
+    options: &PickerOptions,
+) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    color_text_ui(ui, col_srgba, options);
+
+    let grid_id = "oklab_ab_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let mut opaque = *color;
+        opaque.alpha = 1.0;
+
+        if options.controls.alpha {
+            alpha_slider(ui, options, &mut color.alpha, |a| {
+                let mut col = opaque;
+                col.alpha = a;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            alpha_label_ui(ui, options);
+            ui.end_row();
+        }
+
+        if options.controls.preview {
+            let selected: Color32 = color.convert_to::<Srgba>().saturate().into_cint().into();
+            show_color(ui, selected, current_color_size, options.swatch);
+            ui.label("Selected color");
+            ui.end_row();
+        }
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        if options.controls.lightness {
+            color_slider_1d(ui, options, &mut color.col.l, 0.0..=1.0, |l| {
+                let mut col = opaque;
+                col.col.l = l;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            ui.label("Lightness");
+            ui.end_row();
+        }
+
+        if options.controls.area {
+            // The sRGB gamut spans roughly -0.23..0.28 on a and -0.31..0.2 on b.
+            let col = &mut color.col;
+            let response = color_slider_2d(ui, options, &mut col.a, -0.4..=0.4, &mut col.b, -0.4..=0.4, |a, b| {
+                let mut col = opaque;
+                col.col.a = a;
+                col.col.b = b;
+                col.convert_to::<Srgba>().saturate().into_cint().into()
+            });
+            if options.show_perceptual_grid {
+                paint_hue_chroma_grid(ui, response.rect, 0.4);
+            }
+            if let Some(constraint) = &options.constraint {
+                let n = options.render_quality(ui).subdivisions_2d;
+                paint_disallowed(ui, response.rect, -0.4..=0.4, -0.4..=0.4, n, |a, b| {
+                    let mut probe = *color;
+                    probe.col.a = a;
+                    probe.col.b = b;
+                    constraint.allows(probe.convert())
+                });
+            }
+            ui.label("a (green / red) × b (blue / yellow)");
+            ui.end_row();
+        }
+    });
+
+    *color != orig_col
+}
+
+/// The coordinates being edited in a non-Oklch mode, along with the color they were last
+/// converted to. Reusing them while the color is unchanged keeps e.g. the hue around at zero
+/// chroma, where converting back from Oklch would lose it.
+#[derive(Clone)]
+struct ModeEditState<T> {
+    color: OklchA,
+    coords: T,
+}
+
+pub(crate) fn load_mode_coords<T: Copy + 'static>(
+    ui: &Ui,
+    state_id: Id,
+    color: &OklchA,
+    convert: impl FnOnce(&OklchA) -> T,
+) -> T {
+    let cached = temp_get!(ui, state_id, ModeEditState<T>);
+    match cached {
+        Some(state) if state.color == *color => state.coords,
+        _ => convert(color),
+    }
+}
+
+pub(crate) fn store_mode_coords<T: Copy + 'static>(ui: &Ui, state_id: Id, color: OklchA, coords: T) {
+    temp_insert!(ui, state_id, ModeEditState { color, coords });
+}
+
+/// Picker in CIE L\*a\*b\* (or LCh(ab) if `polar`), relative to `white_point`.
+fn color_picker_cielab_2d(
+    ui: &mut Ui,
+    color: &mut OklchA,
+    col_srgba: Srgba,
+    white_point: WhitePoint,
+    polar: bool,
+    options: &PickerOptions,
+) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    color_text_ui(ui, col_srgba, options);
+
+    let state_id = ui.make_persistent_id(("cielab_state", white_point, polar));
+    let mut coords = load_mode_coords(ui, state_id, color, |color| {
+        let lab = cielab::oklch_to_lab(color, white_point);
+        if polar {
+            cielab::lch_from_lab(lab)
+        } else {
+            lab
+        }
+    });
+    let orig_coords = coords;
+
+    let color_at = |coords: [f32; 3], alpha: f32| -> Color32 {
+        let lab = if polar { cielab::lab_from_lch(coords) } else { coords };
+        cielab::lab_to_srgba(lab, alpha, white_point).saturate().into_cint().into()
+    };
+
+    let grid_id = "cielab_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let opaque = coords;
+
+        if options.controls.alpha {
+            alpha_slider(ui, options, &mut color.alpha, |a| color_at(opaque, a));
+            alpha_label_ui(ui, options);
+            ui.end_row();
+        }
+
+        if options.controls.preview {
+            show_color(ui, color_at(opaque, color.alpha), current_color_size, options.swatch);
+            ui.label("Selected color");
+            ui.end_row();
+        }
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        let [l, x, y] = &mut coords;
+        if polar {
+            // sRGB reaches a chroma of about 134 (at blue).
+            if options.controls.hue {
+                color_slider_1d(ui, options, y, 0.0..=360.0, |h| color_at([opaque[0], opaque[1], h], 1.0));
+                ui.label("Hue");
+                ui.end_row();
+            }
+
+            if options.controls.chroma {
+                color_slider_1d(ui, options, x, 0.0..=150.0, |c| color_at([opaque[0], c, opaque[2]], 1.0));
+                ui.label("Chroma");
+                ui.end_row();
+            }
+
+            if options.controls.lightness {
+                color_slider_1d(ui, options, l, 0.0..=100.0, |l| color_at([l, opaque[1], opaque[2]], 1.0));
+                ui.label("Lightness");
+                ui.end_row();
+            }
+
+            if options.controls.area {
+                color_slider_2d(ui, options, x, 0.0..=150.0, l, 0.0..=100.0, |c, l| color_at([l, c, opaque[2]], 1.0));
+                ui.label("Lightness / Chroma");
+                ui.end_row();
+            }
+        } else {
+            if options.controls.lightness {
+                color_slider_1d(ui, options, l, 0.0..=100.0, |l| color_at([l, opaque[1], opaque[2]], 1.0));
+                ui.label("Lightness");
+                ui.end_row();
+            }
+
+            if options.controls.area {
+                color_slider_2d(ui, options, x, -128.0..=128.0, y, -128.0..=128.0, |a, b| {
+                    color_at([opaque[0], a, b], 1.0)
+                });
+                ui.label("a* × b*");
+                ui.end_row();
+            }
+        }
+    });
+
+    if coords != orig_coords {
+        let lab = if polar { cielab::lab_from_lch(coords) } else { coords };
+        *color = options.snap.apply(cielab::lab_to_oklch(lab, color.alpha, white_point));
+    }
+    store_mode_coords(ui, state_id, *color, coords);
+
+    *color != orig_col
+}
+
+/// Picker in HSLuv, where saturation is relative to the sRGB gamut boundary.
+fn color_picker_hsluv_2d(ui: &mut Ui, color: &mut OklchA, col_srgba: Srgba, options: &PickerOptions) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    color_text_ui(ui, col_srgba, options);
+
+    let state_id = ui.make_persistent_id("hsluv_state");
+    let mut coords = load_mode_coords(ui, state_id, color, hsluv::oklch_to_hsluv);
+    let orig_coords = coords;
+
+    let color_at = |coords: [f32; 3], alpha: f32| -> Color32 {
+        hsluv::hsluv_to_srgba(coords, alpha).saturate().into_cint().into()
+    };
+
+    let grid_id = "hsluv_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let opaque = coords;
+
+        if options.controls.alpha {
+            alpha_slider(ui, options, &mut color.alpha, |a| color_at(opaque, a));
+            alpha_label_ui(ui, options);
+            ui.end_row();
+        }
+
+        if options.controls.preview {
+            show_color(ui, color_at(opaque, color.alpha), current_color_size, options.swatch);
+            ui.label("Selected color");
+            ui.end_row();
+        }
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        let [h, s, l] = &mut coords;
+        if options.controls.hue {
+            color_slider_1d(ui, options, h, 0.0..=360.0, |h| color_at([h, opaque[1], opaque[2]], 1.0));
+            ui.label("Hue");
+            ui.end_row();
+        }
+
+        if options.controls.chroma {
+            color_slider_1d(ui, options, s, 0.0..=100.0, |s| color_at([opaque[0], s, opaque[2]], 1.0));
+            ui.label("Saturation");
+            ui.end_row();
+        }
+
+        if options.controls.lightness {
+            color_slider_1d(ui, options, l, 0.0..=100.0, |l| color_at([opaque[0], opaque[1], l], 1.0));
+            ui.label("Lightness");
+            ui.end_row();
+        }
+
+        if options.controls.area {
+            color_slider_2d(ui, options, s, 0.0..=100.0, l, 0.0..=100.0, |s, l| color_at([opaque[0], s, l], 1.0));
+            ui.label("Lightness / Saturation");
+            ui.end_row();
+        }
+    });
+
+    if coords != orig_coords {
+        *color = options.snap.apply(hsluv::hsluv_to_oklch(coords, color.alpha));
+    }
+    store_mode_coords(ui, state_id, *color, coords);
+
+    *color != orig_col
+}
+
+/// "White point" picker: correlated color temperature plus a green–magenta tint.
+fn color_picker_temperature(ui: &mut Ui, color: &mut OklchA, col_srgba: Srgba, options: &PickerOptions) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    color_text_ui(ui, col_srgba, options);
+
+    let state_id = ui.make_persistent_id("temperature_state");
+    let mut coords = load_mode_coords(ui, state_id, color, temperature::oklch_to_temperature);
+    let orig_coords = coords;
+
+    let color_at = |coords: [f32; 3], alpha: f32| -> Color32 {
+        temperature::temperature_to_srgba(coords, alpha).saturate().into_cint().into()
+    };
+
+    let grid_id = "temperature_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let opaque = coords;
+
+        if options.controls.alpha {
+            alpha_slider(ui, options, &mut color.alpha, |a| color_at(opaque, a));
+            alpha_label_ui(ui, options);
+            ui.end_row();
+        }
+
+        if options.controls.preview {
+            show_color(ui, color_at(opaque, color.alpha), current_color_size, options.swatch);
+            ui.label("Selected color");
+            ui.end_row();
+        }
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        let [kelvin, tint, lightness] = &mut coords;
+        if options.controls.hue {
+            let range = temperature::TEMPERATURE_RANGE;
+            color_slider_1d(ui, options, kelvin, range, |k| color_at([k, opaque[1], 1.0], 1.0));
+            ui.label(format!("Temperature ({:.0} K)", opaque[0]));
+            ui.end_row();
+        }
+
+        if options.controls.chroma {
+            color_slider_1d(ui, options, tint, -0.02..=0.02, |t| color_at([opaque[0], t, 1.0], 1.0));
+            ui.label("Tint (green / magenta)");
+            ui.end_row();
+        }
+
+        if options.controls.lightness {
+            color_slider_1d(ui, options, lightness, 0.0..=1.0, |l| color_at([opaque[0], opaque[1], l], 1.0));
+            ui.label("Lightness");
+            ui.end_row();
+        }
+    });
+
+    if coords != orig_coords {
+        *color = options.snap.apply(temperature::temperature_to_oklch(coords, color.alpha));
+    }
+    store_mode_coords(ui, state_id, *color, coords);
+
+    *color != orig_col
+}
+
+/// Picker constrained to the entries of `palette`: the sliders move a free "probe" color, and
+/// the picked color is whichever entry is nearest to it in Oklab.
+pub(crate) fn color_picker_indexed(
+    ui: &mut Ui,
+    color: &mut OklchA,
+    palette: &Palette,
+    index: &mut Option<usize>,
+    options: &PickerOptions,
+) -> bool {
+    profile_function!();
+    let orig_col = *color;
+
+    let state_id = ui.make_persistent_id("indexed_probe");
+    let mut probe = load_mode_coords(ui, state_id, color, |color| *color);
+    let orig_probe = probe;
+
+    // Convert the palette once, rather than for every sample of the sliders.
+    let labs: Vec<OklabA> = palette.entries.iter().map(|entry| entry.color.convert()).collect();
+    let nearest = |probe: OklchA| -> Option<usize> {
+        let probe: OklabA = probe.convert();
+        labs.iter()
+            .map(|lab| {
+                let (dl, da, db) = (lab.col.l - probe.col.l, lab.col.a - probe.col.a, lab.col.b - probe.col.b);
+                dl * dl + da * da + db * db
+            })
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    };
+    let color_at = |probe: OklchA| -> Color32 {
+        match nearest(probe) {
+            Some(i) => palette.entries[i].color.convert::<Srgba>().saturate().into_cint().into(),
+            None => Color32::TRANSPARENT,
+        }
+    };
+
+    let grid_id = "indexed_color_picker";
+
+    crate::Grid::new(grid_id).show(ui, |ui| {
+        let current_color_size = vec2(
+            ui.spacing().slider_width,
+            ui.spacing().interact_size.y * 2.0,
+        );
+
+        let selected: Color32 = color.convert_to::<Srgba>().saturate().into_cint().into();
+        show_color(ui, selected, current_color_size, options.swatch);
+        match index.and_then(|i| palette.entries.get(i)) {
+            Some(entry) => ui.label(format!("Selected: {}", entry.name)),
+            None => ui.label("Selected color"),
+        };
+        ui.end_row();
+
+        ui.separator(); // TODO: fix ever-expansion
+        ui.end_row();
+
+        let opaque = probe;
+
+        use core::f32::consts::PI;
+        color_slider_1d(ui, options, &mut probe.col.h, -PI..=PI, |h| color_at(options.hue_preview(opaque, h)));
+        ui.label("Hue");
+        ui.end_row();
+
+        color_slider_1d(ui, options, &mut probe.col.c, 0.0..=0.5, |c| {
+            let mut col = opaque;
+            col.col.c = c;
+            color_at(col)
+        });
+        ui.label("Chroma");
+        ui.end_row();
+
+        color_slider_1d(ui, options, &mut probe.col.l, 0.0..=1.0, |l| {
+            let mut col = opaque;
+            col.col.l = l;
+            color_at(col)
+        });
+        ui.label("Lightness");
+        ui.end_row();
+
+        let col = &mut probe.col;
+        color_slider_2d_cells(ui, options, &mut col.c, 0.0..=0.5, &mut col.l, 0.0..=1.0, |c, l| {
+            let mut col = opaque;
+            col.col.c = c;
+            col.col.l = l;
+            color_at(col)
+        });
+        ui.label("Lightness / Chroma");
+        ui.end_row();
+    });
+
+    // Clicking a swatch picks that entry directly (the clone keeps the palette itself fixed).
+    let mut swatches = palette.clone();
+    let mut selected = *index;
+    if palette_grid(ui, &mut swatches, &mut selected).changed() {
+        if let Some(entry) = selected.and_then(|i| palette.entries.get(i)) {
+            *index = selected;
+            *color = entry.color;
+            probe = entry.color;
+        }
+    } else if probe != orig_probe {
+        if let Some(i) = nearest(probe) {
+            *index = Some(i);
+            *color = palette.entries[i].color;
+        }
+    }
+    store_mode_coords(ui, state_id, *color, probe);
+
+    *color != orig_col
+}
+
+pub(crate) fn mode_picker_ui(ui: &mut Ui, color: &mut OklchA, col_srgba: Srgba, options: &PickerOptions) -> bool {
+    match options.mode {
+        PickerMode::Oklch => color_picker_oklch_2d(ui, color, col_srgba, options),
+        PickerMode::Oklab => {
+            let mut oklab: OklabA = color.convert();
+            let changed = color_picker_oklab_2d(ui, &mut oklab, col_srgba, options);
+            if changed {
+                *color = options.snap.apply(oklab.convert());
+            }
+            changed
+        }
+        PickerMode::CieLab(white_point) => {
+            color_picker_cielab_2d(ui, color, col_srgba, white_point, false, options)
+        }
+        PickerMode::CieLch(white_point) => {
+            color_picker_cielab_2d(ui, color, col_srgba, white_point, true, options)
+        }
+        PickerMode::Hsluv => color_picker_hsluv_2d(ui, color, col_srgba, options),
+        PickerMode::Temperature => color_picker_temperature(ui, color, col_srgba, options),
+    }
+}
@@ -0,0 +1,705 @@
+//! The panels around the picker's sliders: history, palette, harmonies, previews and tools.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use super::PickerOptions;
+use super::modes::{load_mode_coords, store_mode_coords};
+use crate::sliders::{color_slider_1d, paint_horizontal_gradient};
+use crate::{
+    apply_scheme, background_checkers, best_text_color, cmyk, color_button, composite_over, contrast, convert,
+    extract_palette, generate_categorical, model, output_transform, paint_swatch, show_color, theme, uncomposite,
+    CategoricalConstraints, CompositeSpace, InterpolationSpace, MinContrast, NumberFormat, OklchA, Palette,
+    PaletteGrid, SortKey, Srgba, SwatchStyle,
+};
+
+/// A ballpark CMYK readout with sliders editing `color` through naive CMYK.
+pub(crate) fn cmyk_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let orig_col = *color;
+
+    let state_id = ui.make_persistent_id("cmyk_state");
+    let mut cmyk = load_mode_coords(ui, state_id, color, cmyk::oklch_to_cmyk);
+    let orig_cmyk = cmyk;
+
+    ui.horizontal(|ui| {
+        let percent = |v: f32| (v * 100.0).round();
+        let [c, m, y, k] = [percent(cmyk[0]), percent(cmyk[1]), percent(cmyk[2]), percent(cmyk[3])];
+        ui.label(format!("CMYK (naive): {}%, {}%, {}%, {}%", c, m, y, k));
+
+        if ui.button("📋").on_hover_text("Click to copy").clicked() {
+            ui.output().copied_text = format!("{}, {}, {}, {}", c, m, y, k);
+        }
+    });
+
+    crate::Grid::new("cmyk_sliders").show(ui, |ui| {
+        let opaque = cmyk;
+        let names = ["Cyan", "Magenta", "Yellow", "Key (black)"];
+        for (i, (value, name)) in cmyk.iter_mut().zip(names.iter()).enumerate() {
+            color_slider_1d(ui, options, value, 0.0..=1.0, |v| {
+                let mut cmyk = opaque;
+                cmyk[i] = v;
+                cmyk::cmyk_to_srgba(cmyk).saturate().into_cint().into()
+            });
+            ui.label(*name);
+            ui.end_row();
+        }
+    });
+
+    if cmyk != orig_cmyk {
+        *color = options.snap.apply(cmyk::cmyk_to_oklch(cmyk, color.alpha));
+    }
+    store_mode_coords(ui, state_id, *color, cmyk);
+
+    *color != orig_col
+}
+
+/// The Oklab gradient from `color` to a pinned second color, with buttons to pin the current
+/// color as the other end and to swap the ends.
+pub(crate) fn gradient_pair_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let pinned_id = ui.make_persistent_id("gradient_pair");
+    let mut pinned = temp_get!(ui, pinned_id, OklchA);
+    let orig_col = *color;
+
+    if let Some(end) = pinned {
+        let size = vec2(ui.spacing().slider_width, ui.spacing().interact_size.y * 1.5);
+        let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+        background_checkers(ui.painter(), rect, options.swatch.backdrop);
+        let start = *color;
+        let n = options.render_quality(ui).subdivisions_1d;
+        paint_horizontal_gradient(ui.painter(), rect, n, |t| {
+            convert::oklab_lerp(start, end, t).convert::<Srgba>().saturate().into_cint().into()
+        });
+    }
+    ui.horizontal(|ui| {
+        if ui.button("📌 Pin this color").clicked() {
+            pinned = Some(*color);
+        }
+        if let Some(end) = pinned {
+            if ui.button("⇄ Swap").clicked() {
+                pinned = Some(*color);
+                *color = end;
+            }
+            if ui.button("Clear").clicked() {
+                pinned = None;
+            }
+        }
+    });
+
+    match pinned {
+        Some(end) => temp_insert!(ui, pinned_id, end),
+        None => temp_remove!(ui, pinned_id, OklchA),
+    }
+    *color != orig_col
+}
+
+#[derive(Clone, Copy)]
+struct MixerState {
+    other: OklchA,
+    /// How much of `other` is mixed in, from 0 to 1.
+    amount: f32,
+    space: InterpolationSpace,
+}
+
+/// A slider blending `color` with a second color, and "Use mix" to make the blend the color.
+pub(crate) fn mixer_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let state_id = ui.make_persistent_id("mixer");
+    let mut state = temp_get!(ui, state_id, MixerState).unwrap_or(MixerState {
+        other: OklchA::new(1.0, 0.0, 0.0, 1.0),
+        amount: 0.3,
+        space: InterpolationSpace::Oklab,
+    });
+    let to_color32 = |c: OklchA| -> Color32 { c.convert::<Srgba>().saturate().into_cint().into() };
+    let swatch_size = ui.spacing().interact_size;
+
+    ui.horizontal(|ui| {
+        ui.label("With");
+        let presets = [
+            ("White", OklchA::new(1.0, 0.0, 0.0, 1.0)),
+            ("Gray", OklchA::new(0.6, 0.0, 0.0, 1.0)),
+            ("Black", OklchA::new(0.0, 0.0, 0.0, 1.0)),
+        ];
+        for &(name, preset) in &presets {
+            if color_button(ui, to_color32(preset), options.swatch).on_hover_text(name).clicked() {
+                state.other = preset;
+            }
+        }
+        if ui.button("📌 Pin this color").clicked() {
+            state.other = *color;
+        }
+        show_color(ui, to_color32(state.other), swatch_size, options.swatch);
+    });
+
+    // Not a combo box: its popup would replace the picker's, closing it.
+    ui.horizontal_wrapped(|ui| {
+        ui.radio_value(&mut state.space, InterpolationSpace::Oklab, "Oklab");
+        ui.radio_value(&mut state.space, InterpolationSpace::OklchShorter, "Oklch (shorter hue)");
+        ui.radio_value(&mut state.space, InterpolationSpace::OklchLonger, "Oklch (longer hue)");
+    });
+
+    let base = *color;
+    let (other, space) = (state.other, state.space);
+    let mix_at = |t: f32| space.interpolate(base, other, t);
+    ui.horizontal(|ui| {
+        color_slider_1d(ui, options, &mut state.amount, 0.0..=1.0, |t| to_color32(mix_at(t)));
+        ui.label(format!("{:.0}%", state.amount * 100.0));
+    });
+
+    let mix = mix_at(state.amount);
+    let mut picked = false;
+    ui.horizontal(|ui| {
+        show_color(ui, to_color32(mix), swatch_size, options.swatch).on_hover_text(convert::to_hex(mix));
+        picked = ui.add_enabled(mix != *color, Button::new("Use mix")).clicked();
+    });
+    if picked {
+        *color = options.snap.apply(mix);
+    }
+
+    temp_insert!(ui, state_id, state);
+    picked
+}
+
+#[derive(Clone, Copy)]
+struct CompositingState {
+    background: OklchA,
+    space: CompositeSpace,
+    /// The alpha of the overlay, or `None` for the most transparent one.
+    alpha: Option<f32>,
+}
+
+/// `color` composited over a background, and the overlay compositing over the background to
+/// `color`, with "Use overlay" making it the color.
+pub(crate) fn compositing_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let state_id = ui.make_persistent_id("compositing");
+    let mut state = temp_get!(ui, state_id, CompositingState).unwrap_or(CompositingState {
+        background: OklchA::new(1.0, 0.0, 0.0, 1.0),
+        space: CompositeSpace::default(),
+        alpha: None,
+    });
+    let to_color32 = |c: OklchA| -> Color32 { c.convert::<Srgba>().saturate().into_cint().into() };
+    let swatch_size = ui.spacing().interact_size;
+    let result_ui = |ui: &mut Ui, label: &str, result: OklchA| {
+        ui.horizontal(|ui| {
+            show_color(ui, to_color32(result), swatch_size, options.swatch);
+            let hex = convert::to_hex(result);
+            ui.label(format!("{}: {}", label, hex));
+            if ui.button("📋").on_hover_text("Click to copy").clicked() {
+                ui.output().copied_text = hex;
+            }
+        });
+    };
+
+    ui.horizontal(|ui| {
+        ui.label("Background");
+        let presets = [("White", OklchA::new(1.0, 0.0, 0.0, 1.0)), ("Black", OklchA::new(0.0, 0.0, 0.0, 1.0))];
+        for &(name, preset) in &presets {
+            if color_button(ui, to_color32(preset), options.swatch).on_hover_text(name).clicked() {
+                state.background = preset;
+            }
+        }
+        if ui.button("📌 Pin this color").clicked() {
+            state.background = OklchA::new(color.col.l, color.col.c, color.col.h, 1.0);
+        }
+        show_color(ui, to_color32(state.background), swatch_size, options.swatch);
+    });
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut state.space, CompositeSpace::EncodedSrgb, "Encoded sRGB");
+        ui.radio_value(&mut state.space, CompositeSpace::LinearSrgb, "Linear sRGB");
+    });
+
+    result_ui(ui, "Over background", composite_over(*color, state.background, state.space));
+
+    ui.horizontal(|ui| {
+        let mut fixed = state.alpha.is_some();
+        ui.checkbox(&mut fixed, "Fixed overlay alpha");
+        let mut alpha = state.alpha.unwrap_or(0.5);
+        if fixed {
+            ui.add(DragValue::new(&mut alpha).speed(0.005).clamp_range(0.0..=1.0));
+        }
+        state.alpha = Some(alpha).filter(|_| fixed);
+    });
+    let overlay = uncomposite(*color, state.background, state.space, state.alpha);
+    let mut picked = false;
+    match overlay {
+        Some(overlay) => {
+            result_ui(ui, "Overlay", overlay);
+            if ui.add_enabled(overlay != *color, Button::new("Use overlay")).clicked() {
+                *color = options.snap.apply(overlay);
+                picked = true;
+            }
+        }
+        None => {
+            ui.add(Label::new("No overlay with this alpha is in the sRGB gamut").italics());
+        }
+    }
+
+    temp_insert!(ui, state_id, state);
+    picked
+}
+
+/// Strips of each background with the color composited over its right part.
+pub(crate) fn blend_preview_ui(ui: &mut Ui, color: OklchA, backgrounds: &[Color32]) {
+    let color = Rgba::from(Color32::from_cint(color.convert::<Srgba>().saturate().to_u8().into()));
+    for &background in backgrounds {
+        // Premultiplied "over", in linear light like the GPU does it.
+        let blended = Color32::from(color + Rgba::from(background) * (1.0 - color.a()));
+        let size = vec2(ui.spacing().slider_width, ui.spacing().interact_size.y);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        let split = rect.left() + rect.width() / 4.0;
+        let painter = ui.painter();
+        let output = output_transform(ui.ctx());
+        painter.rect_filled(Rect::from_min_max(rect.min, pos2(split, rect.bottom())), 0.0, output.apply(background));
+        painter.rect_filled(Rect::from_min_max(pos2(split, rect.top()), rect.max), 0.0, output.apply(blended));
+        let hex = |c: Color32| format!("#{:02x}{:02x}{:02x}", c.r(), c.g(), c.b());
+        response.on_hover_text(format!("{} over {}", hex(blended), hex(background)));
+    }
+}
+
+pub(crate) fn contrast_ui(ui: &mut Ui, color: OklchA, backgrounds: &[Color32]) {
+    let color = Color32::from_cint(color.convert::<Srgba>().saturate().to_u8().into()).to_opaque();
+    let others = if backgrounds.is_empty() {
+        &[Color32::WHITE, Color32::BLACK][..]
+    } else {
+        backgrounds
+    };
+    let hex = |c: Color32| format!("#{:02x}{:02x}{:02x}", c.r(), c.g(), c.b());
+    Grid::new("contrast").show(ui, |ui| {
+        ui.label("");
+        ui.label("WCAG 2").on_hover_text("Contrast ratio, 4.5 or more for body text (AA)");
+        ui.label("APCA Lc").on_hover_text("Lightness contrast, 75 or more (either sign) for body text");
+        ui.end_row();
+        for &other in others {
+            let pairs = [
+                (color, other, format!("Text on {}", hex(other))),
+                (other, color, format!("{} text on it", hex(other))),
+            ];
+            for (text, bg, name) in pairs.iter() {
+                let (text, bg) = (*text, *bg);
+                ui.label(name);
+                ui.monospace(format!("{:.2}:1", contrast::wcag_contrast_ratio(text, bg)));
+                ui.monospace(format!("{:.1}", contrast::apca_contrast(text, bg)));
+                ui.end_row();
+            }
+        }
+    });
+}
+
+pub(crate) fn diagnostics_ui(ui: &mut Ui, color: OklchA, format: &NumberFormat) {
+    let rows = [
+        ("CIE Y", convert::cie_luminance(color), "Luminance relative to the D65 white point"),
+        (
+            "WCAG luminance",
+            convert::wcag_relative_luminance(color),
+            "Relative luminance as used for WCAG contrast ratios",
+        ),
+        ("Oklab L", color.col.l, "Perceived lightness"),
+    ];
+    Grid::new("diagnostics").show(ui, |ui| {
+        for (name, value, hover) in rows.iter() {
+            ui.label(*name).on_hover_text(*hover);
+            let text = format.number(*value);
+            ui.monospace(&text);
+            if ui.button("📋").on_hover_text("Click to copy").clicked() {
+                ui.output().copied_text = text;
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Previews the dark and light [`Visuals`] generated by [`theme::visuals_from_seed`].
+pub(crate) fn theme_preview_ui(ui: &mut Ui, seed: OklchA) {
+    ui.horizontal(|ui| {
+        for &dark_mode in &[true, false] {
+            ui.scope(|ui| {
+                ui.style_mut().visuals = theme::visuals_from_seed(seed, dark_mode);
+                let fill = ui.visuals().widgets.noninteractive.bg_fill;
+                Frame::group(ui.style()).fill(fill).show(ui, |ui| {
+                    ui.label(if dark_mode { "Dark" } else { "Light" });
+                    let _ = ui.button("Button");
+                    let _ = ui.selectable_label(true, "Selected");
+                    ui.hyperlink_to("Hyperlink", "https://github.com/emilk/egui");
+                    ui.code("code");
+                });
+            });
+        }
+    });
+}
+
+/// A row of swatches per scheme of [`PickerOptions::harmonies`]; returns whether one was picked.
+/// The hovered one is put in `hovered`.
+pub(crate) fn harmonies_ui(
+    ui: &mut Ui,
+    color: &mut OklchA,
+    options: &PickerOptions,
+    hovered: &mut Option<OklchA>,
+) -> bool {
+    let base = *color;
+    let mut picked = None;
+    Grid::new("harmonies").show(ui, |ui| {
+        for scheme in &options.harmonies {
+            ui.label(&scheme.name);
+            ui.horizontal(|ui| {
+                for harmony in apply_scheme(base, scheme) {
+                    let color32: Color32 = harmony.convert::<Srgba>().saturate().into_cint().into();
+                    let response = color_button(ui, color32, options.swatch)
+                        .on_hover_text(convert::to_css_oklch(harmony));
+                    if response.hovered() {
+                        *hovered = Some(harmony);
+                    }
+                    if response.clicked() {
+                        picked = Some(harmony);
+                    }
+                }
+            });
+            ui.end_row();
+        }
+    });
+    match picked {
+        Some(harmony) if harmony != *color => {
+            *color = options.snap.apply(harmony);
+            true
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn context_preview_ui(ui: &mut Ui, color: OklchA) {
+    let color32: Color32 = color.convert::<Srgba>().saturate().into_cint().into();
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(ui.spacing().interact_size * vec2(2.0, 1.0), Sense::hover());
+        let text = best_text_color(color32, &[Color32::WHITE, Color32::BLACK], MinContrast::Wcag2(4.5));
+        let fill = output_transform(ui.ctx()).apply(color32);
+        ui.painter()
+            .rect_filled(rect, ui.visuals().widgets.inactive.corner_radius, fill);
+        ui.painter()
+            .text(rect.center(), Align2::CENTER_CENTER, "Button", TextStyle::Button, text);
+
+        for &bg in &[Color32::WHITE, Color32::from_gray(27)] {
+            Frame::group(ui.style()).fill(bg).show(ui, |ui| {
+                ui.add(Label::new("Text").text_color(color32));
+            });
+        }
+
+        let (rect, _) = ui.allocate_exact_size(ui.spacing().interact_size, Sense::hover());
+        ui.painter().rect_stroke(rect.shrink(2.0), 0.0, Stroke::new(1.0, color32));
+    });
+}
+
+/// The swatch of `color` in the middle of a light and a dark surround, like UI chrome of either
+/// theme would surround it.
+pub(crate) fn surround_preview_ui(ui: &mut Ui, color: OklchA, style: SwatchStyle) {
+    let swatch = Vec2::splat(ui.spacing().interact_size.y * 2.0);
+    let (rect, _) = ui.allocate_exact_size(swatch * vec2(6.0, 3.0), Sense::hover());
+    let color32: Color32 = color.convert::<Srgba>().saturate().into_cint().into();
+    let painter = ui.painter();
+    // Name, surround and label color of each half.
+    let surrounds = [
+        ("Light", Color32::from_gray(245), Color32::from_gray(60)),
+        ("Dark", Color32::from_gray(24), Color32::from_gray(200)),
+    ];
+    for (i, &(name, surround, text)) in surrounds.iter().enumerate() {
+        let min = rect.min + vec2(i as f32 * rect.width() / 2.0, 0.0);
+        let half = Rect::from_min_size(min, rect.size() * vec2(0.5, 1.0));
+        painter.rect_filled(half, 0.0, surround);
+        paint_swatch(painter, Rect::from_center_size(half.center(), swatch), color32, style);
+        let corner = half.left_top() + Vec2::splat(4.0);
+        painter.text(corner, Align2::LEFT_TOP, name, TextStyle::Small, text);
+    }
+}
+
+/// Swatches of `palette`, picking a swatch's color when clicked, plus buttons to edit the palette.
+pub(crate) fn palette_panel_ui(
+    ui: &mut Ui,
+    color: &mut OklchA,
+    palette: &mut Palette,
+    on_load: Option<&mut dyn FnMut(&mut Palette)>,
+    on_save: Option<&mut dyn FnMut(&Palette)>,
+    image: Option<&[Color32]>,
+    hovered: &mut Option<OklchA>,
+) -> bool {
+    let orig_col = *color;
+
+    let mut selected = palette.entries.iter().position(|entry| entry.color == *color);
+    let mut hovered_entry = None;
+    let grid = PaletteGrid::new(palette, &mut selected)
+        .reorderable(true)
+        .hovered(&mut hovered_entry)
+        .show(ui);
+    if grid.changed() {
+        if let Some(entry) = selected.and_then(|i| palette.entries.get(i)) {
+            *color = entry.color;
+        }
+    }
+    if let Some(entry) = hovered_entry.and_then(|i| palette.entries.get(i)) {
+        *hovered = Some(entry.color);
+    }
+
+    if let Some(entry) = selected.and_then(|i| palette.entries.get_mut(i)) {
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut entry.name);
+        });
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Add current").clicked() {
+            palette.push_auto_named(*color);
+        }
+        if let Some(i) = selected {
+            if ui.button("Remove").clicked() {
+                palette.entries.remove(i);
+            }
+        }
+        if let Some(load) = on_load {
+            if ui.button("Load…").clicked() {
+                load(palette);
+            }
+        }
+        if let Some(save) = on_save {
+            if ui.button("Save…").clicked() {
+                save(palette);
+            }
+        }
+    });
+    // Not a combo box: its popup would replace the picker's, closing it.
+    CollapsingHeader::new("Sort")
+        .id_source(ui.make_persistent_id("palette_sort"))
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for &(key, label) in &[
+                    (SortKey::Hue, "By hue"),
+                    (SortKey::Lightness, "By lightness"),
+                    (SortKey::Chroma, "By chroma"),
+                    (SortKey::SmoothPath, "Smooth path"),
+                ] {
+                    if ui.button(label).clicked() {
+                        palette.sort_by(key);
+                    }
+                }
+            });
+        });
+
+    if let Some(pixels) = image {
+        let k_id = ui.make_persistent_id("palette_extract_k");
+        let mut k = temp_get!(ui, k_id, usize).unwrap_or(8);
+        ui.horizontal(|ui| {
+            ui.add(DragValue::new(&mut k).clamp_range(1..=64));
+            if ui.button("Extract from image").clicked() {
+                palette.entries.clear();
+                for color in extract_palette(pixels, k) {
+                    palette.push_auto_named(color);
+                }
+            }
+        });
+        temp_insert!(ui, k_id, k);
+    }
+
+    let n_id = ui.make_persistent_id("palette_categorical_n");
+    let mut n = temp_get!(ui, n_id, usize).unwrap_or(8);
+    ui.horizontal(|ui| {
+        ui.add(DragValue::new(&mut n).clamp_range(1..=32));
+        if ui
+            .button("Generate categorical")
+            .on_hover_text("Replace the palette with colors as distinct from each other as possible")
+            .clicked()
+        {
+            palette.entries.clear();
+            for color in generate_categorical(n, &CategoricalConstraints::default()) {
+                palette.push_auto_named(color);
+            }
+        }
+    });
+    temp_insert!(ui, n_id, n);
+
+    *color != orig_col
+}
+
+/// Shows the palette entry nearest to `color`, how far off it is, and a button to snap to it.
+pub(crate) fn nearest_palette_ui(ui: &mut Ui, color: &mut OklchA, palette: &Palette) -> bool {
+    let entry = match palette.nearest(*color).map(|i| &palette.entries[i]) {
+        Some(entry) => entry,
+        None => return false,
+    };
+    let delta_e = convert::delta_e_ok(*color, entry.color);
+
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        let size = Vec2::splat(ui.spacing().interact_size.y);
+        let entry_color: Color32 = entry.color.convert::<Srgba>().saturate().into_cint().into();
+        show_color(ui, entry_color, size, SwatchStyle::default());
+        // Below about 0.002 the difference is just float noise from round trips.
+        if delta_e < 0.002 {
+            ui.label(format!("{} (exact)", entry.name));
+        } else {
+            ui.label(format!("Nearest: {} (ΔE {:.3})", entry.name, delta_e));
+            if ui.button("Snap to it").clicked() {
+                *color = entry.color;
+                changed = true;
+            }
+        }
+    });
+    changed
+}
+
+/// The Oklch difference `[ΔL, ΔC, Δh, Δalpha]` from `from` to `to`.
+pub(crate) fn oklch_delta(from: &OklchA, to: &OklchA) -> [f32; 4] {
+    [
+        to.col.l - from.col.l,
+        to.col.c - from.col.c,
+        to.col.h - from.col.h,
+        to.alpha - from.alpha,
+    ]
+}
+
+/// `base` offset by `[ΔL, ΔC, Δh, Δalpha]`, clamped to valid values.
+pub(crate) fn offset_oklch(base: OklchA, [dl, dc, dh, da]: [f32; 4]) -> OklchA {
+    let mut color = base;
+    color.col.l = (base.col.l + dl).clamp(0.0, 1.0);
+    color.col.c = (base.col.c + dc).max(0.0);
+    color.col.h = convert::wrap_hue(base.col.h + dh);
+    color.alpha = (base.alpha + da).clamp(0.0, 1.0);
+    color
+}
+
+/// Adds `color` as the newest entry of the history strip, if it has one.
+pub(crate) fn record_history(ctx: &CtxRef, options: &PickerOptions, color: OklchA) {
+    let id = match options.history_id {
+        Some(id) if options.history_len > 0 => id,
+        _ => return,
+    };
+    let mut history = temp_get!(ctx, id, Vec<OklchA>).unwrap_or_default();
+    model::push_history(&mut history, color, options.history_len);
+    temp_insert!(ctx, id, history);
+}
+
+/// The history strip, newest first, with a preview of the hovered entry beside the current
+/// color. Clicking an entry restores it.
+pub(crate) fn history_ui(
+    ui: &mut Ui,
+    color: &mut OklchA,
+    options: &PickerOptions,
+    hovered_out: &mut Option<OklchA>,
+) -> bool {
+    let history = match options.history_id {
+        Some(id) => temp_get!(ui, id, Vec<OklchA>).unwrap_or_default(),
+        None => return false,
+    };
+    if history.is_empty() {
+        return false;
+    }
+    let to_color32 = |c: OklchA| -> Color32 { c.convert::<Srgba>().saturate().into_cint().into() };
+
+    let orig_col = *color;
+    let mut hovered = None;
+    ui.horizontal(|ui| {
+        let size = Vec2::splat(ui.spacing().interact_size.y);
+        for entry in history.iter().rev() {
+            let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+            paint_swatch(ui.painter(), rect, to_color32(*entry), options.swatch);
+            if response.hovered() {
+                hovered = Some(*entry);
+                ui.painter().rect_stroke(rect, 0.0, ui.visuals().selection.stroke);
+            }
+            if response.on_hover_text(convert::to_hex(*entry)).clicked() {
+                *color = *entry;
+            }
+        }
+    });
+
+    let size = vec2(ui.spacing().slider_width, ui.spacing().interact_size.y);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+    let current = Rect::from_min_max(rect.left_top(), rect.center_bottom());
+    let preview = Rect::from_min_max(rect.center_top(), rect.right_bottom());
+    paint_swatch(ui.painter(), current, to_color32(orig_col), options.swatch);
+    paint_swatch(ui.painter(), preview, to_color32(hovered.unwrap_or(orig_col)), options.swatch);
+    response.on_hover_text("Current color, and the hovered history entry");
+    if hovered.is_some() {
+        *hovered_out = hovered;
+    }
+
+    *color != orig_col
+}
+
+/// Steps of the quick adjust buttons, see [`ColorEditButton::quick_adjust`](crate::ColorEditButton::quick_adjust).
+const QUICK_LIGHTNESS_STEP: f32 = 0.05;
+const QUICK_CHROMA_STEP: f32 = 0.02;
+
+/// Buttons nudging the lightness and chroma of `color` by a fixed step, keeping its hue.
+pub(crate) fn quick_adjust_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let orig_col = *color;
+    ui.horizontal(|ui| {
+        let actions: [(&str, &str, fn(OklchA, f32) -> OklchA, f32); 4] = [
+            ("Lighten", "ΔL +0.05", convert::lighten, QUICK_LIGHTNESS_STEP),
+            ("Darken", "ΔL −0.05", convert::darken, QUICK_LIGHTNESS_STEP),
+            ("Saturate", "ΔC +0.02", convert::saturate, QUICK_CHROMA_STEP),
+            ("Desaturate", "ΔC −0.02", convert::desaturate, QUICK_CHROMA_STEP),
+        ];
+        for (label, hover, adjust, step) in actions.iter() {
+            if ui.small_button(*label).on_hover_text(*hover).clicked() {
+                *color = options.snap.apply(adjust(*color, *step));
+            }
+        }
+    });
+    *color != orig_col
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PopupTab {
+    Pick,
+    Adjust,
+}
+
+/// The color relative adjustments are made from, the adjustments, and the result of applying
+/// them. If the color no longer matches the result it was changed elsewhere, so we start over.
+#[derive(Clone)]
+struct AdjustState {
+    base: OklchA,
+    deltas: [f32; 4],
+    applied: OklchA,
+}
+
+/// Sliders for relative changes (lighten/darken, saturate/desaturate, hue shift, fade) applied
+/// live to `color`, with a before/after preview.
+pub(crate) fn adjust_ui(ui: &mut Ui, color: &mut OklchA, options: &PickerOptions) -> bool {
+    let state_id = ui.make_persistent_id("adjust_state");
+    let cached = temp_get!(ui, state_id, AdjustState);
+    let mut state = match cached {
+        Some(state) if state.applied == *color => state,
+        _ => AdjustState {
+            base: *color,
+            deltas: [0.0; 4],
+            applied: *color,
+        },
+    };
+
+    ui.horizontal(|ui| {
+        let size = vec2(ui.spacing().slider_width / 2.0, ui.spacing().interact_size.y * 2.0);
+        let before: Color32 = state.base.convert::<Srgba>().saturate().into_cint().into();
+        show_color(ui, before, size, options.swatch).on_hover_text("Before");
+        let after: Color32 = color.convert::<Srgba>().saturate().into_cint().into();
+        show_color(ui, after, size, options.swatch).on_hover_text("After");
+    });
+
+    use core::f32::consts::PI;
+    let [dl, dc, dh, da] = &mut state.deltas;
+    let mut edited = ui.add(Slider::new(dl, -1.0..=1.0).text("ΔL (lightness)")).changed();
+    edited |= ui.add(Slider::new(dc, -0.5..=0.5).text("ΔC (chroma)")).changed();
+    edited |= ui.add(Slider::new(dh, -PI..=PI).text("Δh (hue)")).changed();
+    edited |= ui.add(Slider::new(da, -1.0..=1.0).text("Δ alpha")).changed();
+
+    if ui.button("Reset").clicked() {
+        state.deltas = [0.0; 4];
+        edited = true;
+    }
+
+    // Only on edits, as snapping (or wrapping the hue) could otherwise change the color just by
+    // showing the tab.
+    if edited {
+        *color = options.snap.apply(offset_oklch(state.base, state.deltas));
+    }
+    state.applied = *color;
+    temp_insert!(ui, state_id, state);
+
+    edited
+}
@@ -0,0 +1,596 @@
+//! The 1D and 2D sliders the pickers are built from, and the guides drawn over them.
+
+use std::ops::RangeInclusive;
+
+use egui::*;
+
+use crate::picker::PickerOptions;
+use crate::{
+    background_checkers, contrast, convert, output_transform, show_color, ContrastMetric, HueUnit, NumberFormat,
+};
+
+/// Fills `rect` with `color_at(t)` for `t` going from 0 on the left to 1 on the right.
+/// `n` is the number of segments.
+pub(crate) fn paint_horizontal_gradient(painter: &Painter, rect: Rect, n: u32, color_at: impl Fn(f32) -> Color32) {
+    #![allow(clippy::identity_op)]
+
+    let n = n.max(1);
+    let output = output_transform(painter.ctx());
+    let mut mesh = Mesh::default();
+    mesh.reserve_vertices(2 * (n as usize + 1));
+    mesh.reserve_triangles(2 * n as usize);
+    for i in 0..=n {
+        let t = i as f32 / (n as f32);
+        let color = output.apply(color_at(t));
+        let x = egui::lerp(rect.left()..=rect.right(), t);
+        mesh.colored_vertex(pos2(x, rect.top()), color);
+        mesh.colored_vertex(pos2(x, rect.bottom()), color);
+        if i < n {
+            mesh.add_triangle(2 * i + 0, 2 * i + 1, 2 * i + 2);
+            mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+        }
+    }
+    painter.add(Shape::mesh(mesh));
+}
+
+/// The shape of a slider marker, see [`MarkerStyle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MarkerShape {
+    /// Pointing at the value; along the bottom edge of 1D sliders.
+    Triangle,
+    Circle,
+    /// A bar across 1D sliders, or a crosshair in the 2D area.
+    Bar,
+}
+
+/// How the markers showing the current value on the sliders are drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarkerStyle {
+    pub shape_1d: MarkerShape,
+    pub shape_2d: MarkerShape,
+    /// Scale relative to the default size, which follows the size of the slider.
+    pub size: f32,
+    /// Outline width; `None` uses the width of the widget foreground stroke.
+    pub stroke_width: Option<f32>,
+    /// Outline color; `None` picks black or white, whichever contrasts with the marked color.
+    pub stroke_color: Option<Color32>,
+}
+
+impl Default for MarkerStyle {
+    fn default() -> Self {
+        Self {
+            shape_1d: MarkerShape::Triangle,
+            shape_2d: MarkerShape::Circle,
+            size: 1.0,
+            stroke_width: None,
+            stroke_color: None,
+        }
+    }
+}
+
+impl MarkerStyle {
+    fn stroke(&self, default_width: f32, fill: Color32) -> Stroke {
+        Stroke::new(
+            self.stroke_width.unwrap_or(default_width),
+            self.stroke_color.unwrap_or_else(|| {
+                contrast::most_contrasting(fill, &[Color32::BLACK, Color32::WHITE], ContrastMetric::Wcag2)
+            }),
+        )
+    }
+}
+
+/// Where a drag of a color slider started, kept in temp memory while dragging.
+#[derive(Clone, Copy)]
+struct DragAnchor {
+    pointer: Pos2,
+    t: Vec2,
+    fine: bool,
+    lock: bool,
+    /// With `lock`: `Some(true)` once locked to the x axis, `Some(false)` for the y axis.
+    locked_to_x: Option<bool>,
+}
+
+/// The new normalized position (x to the right, y upwards, both `0..=1`) of a slider currently
+/// at `t`, or `None` if it isn't being interacted with.
+///
+/// Normally the slider follows the pointer. When dragging with the fine-adjust modifiers held,
+/// or in `relative` mode, pointer movement is instead applied relative to where the value was
+/// when the drag (or the fine adjustment) started, scaled by 0.1 for fine adjustment.
+///
+/// With `axis_lock`, holding Ctrl while dragging restricts movement to whichever axis the
+/// pointer first moved further along.
+fn slider_drag(
+    ui: &Ui,
+    response: &Response,
+    rect: Rect,
+    t: Vec2,
+    options: &PickerOptions,
+    relative: bool,
+    axis_lock: bool,
+) -> Option<Vec2> {
+    let anchor_id = response.id.with("drag_anchor");
+    if !response.dragged() {
+        temp_remove!(ui, anchor_id, DragAnchor);
+    }
+    let pointer = response.interact_pointer_pos()?;
+
+    let fine = response.dragged() && options.fine_adjust_held(ui);
+    let lock = axis_lock && response.dragged() && ui.input().modifiers.ctrl;
+    if !fine && !relative && !lock {
+        return Some(vec2(
+            remap_clamp(pointer.x, rect.left()..=rect.right(), 0.0..=1.0),
+            remap_clamp(pointer.y, rect.bottom()..=rect.top(), 0.0..=1.0),
+        ));
+    }
+    if !response.dragged() {
+        return None;
+    }
+
+    let stored = temp_get!(ui, anchor_id, DragAnchor);
+    // Re-anchor when the modifiers change, so the value doesn't jump.
+    let mut anchor = match stored {
+        Some(anchor) if anchor.fine == fine && anchor.lock == lock => anchor,
+        _ => DragAnchor {
+            pointer,
+            t,
+            fine,
+            lock,
+            locked_to_x: None,
+        },
+    };
+
+    let moved = pointer - anchor.pointer;
+    if lock && anchor.locked_to_x.is_none() && moved.length() > 2.0 {
+        anchor.locked_to_x = Some(moved.x.abs() >= moved.y.abs());
+    }
+    temp_insert!(ui, anchor_id, anchor);
+
+    let scale = if fine { 0.1 } else { 1.0 };
+    let mut delta = vec2(moved.x / rect.width(), -moved.y / rect.height());
+    if lock {
+        match anchor.locked_to_x {
+            Some(true) => delta.y = 0.0,
+            Some(false) => delta.x = 0.0,
+            None => delta = Vec2::ZERO,
+        }
+    }
+    let t = anchor.t + delta * scale;
+    Some(vec2(t.x.clamp(0.0, 1.0), t.y.clamp(0.0, 1.0)))
+}
+
+pub(crate) fn color_slider_1d(
+    ui: &mut Ui,
+    options: &PickerOptions,
+    value: &mut f32,
+    range: RangeInclusive<f32>,
+    color_at: impl Fn(f32) -> Color32,
+) -> Response {
+    profile_function!();
+    // Taller sliders give fingers something to hit.
+    let height_factor = if options.is_touch(ui) { 3.0 } else { 2.0 };
+    let desired_size = vec2(
+        ui.spacing().slider_width,
+        ui.spacing().interact_size.y * height_factor,
+    );
+    let (rect, response) = ui.allocate_at_least(desired_size, Sense::click_and_drag());
+
+    let t = vec2(remap_clamp(*value, range.clone(), 0.0..=1.0), 0.5);
+    if let Some(t) = slider_drag(ui, &response, rect, t, options, false, false) {
+        *value = egui::lerp(range.clone(), t.x);
+    }
+
+    let visuals = ui.style().interact(&response);
+
+    background_checkers(ui.painter(), rect, options.swatch.backdrop); // for alpha:
+
+    // fill color:
+    let n = options.render_quality(ui).subdivisions_1d;
+    paint_horizontal_gradient(ui.painter(), rect, n, |t| color_at(egui::lerp(range.clone(), t)));
+
+    ui.painter().rect_stroke(rect, 0.0, visuals.bg_stroke); // outline
+
+    {
+        // Show where the slider is at:
+        let x = egui::lerp(rect.left()..=rect.right(), remap_clamp(*value, range.clone(), 0.0..=1.0));
+        let marker = &options.marker;
+        let r = rect.height() / 4.0 * marker.size;
+        let picked_color = output_transform(ui.ctx()).apply(color_at(*value));
+        let stroke = marker.stroke(visuals.fg_stroke.width, picked_color);
+        let center = pos2(x, rect.center().y);
+        match marker.shape_1d {
+            MarkerShape::Triangle => {
+                ui.painter().add(Shape::polygon(
+                    vec![pos2(x - r, rect.bottom()), pos2(x + r, rect.bottom()), center],
+                    picked_color,
+                    stroke,
+                ));
+            }
+            MarkerShape::Circle => ui.painter().circle(center, r, picked_color, stroke),
+            MarkerShape::Bar => {
+                let bar = Rect::from_center_size(center, vec2(r, rect.height()));
+                ui.painter().rect(bar, 0.0, picked_color, stroke);
+            }
+        }
+    }
+
+    response
+}
+
+pub(crate) fn color_slider_2d(
+    ui: &mut Ui,
+    options: &PickerOptions,
+    x_value: &mut f32,
+    x_range: RangeInclusive<f32>,
+    y_value: &mut f32,
+    y_range: RangeInclusive<f32>,
+    color_at: impl Fn(f32, f32) -> Color32,
+) -> Response {
+    color_slider_2d_impl(ui, options, x_value, x_range, y_value, y_range, color_at, false)
+}
+
+/// Like [`color_slider_2d`], but fills each of the n×n cells with the flat color at its center
+/// instead of blending between vertices, for discrete `color_at` such as palette lookups.
+pub(crate) fn color_slider_2d_cells(
+    ui: &mut Ui,
+    options: &PickerOptions,
+    x_value: &mut f32,
+    x_range: RangeInclusive<f32>,
+    y_value: &mut f32,
+    y_range: RangeInclusive<f32>,
+    color_at: impl Fn(f32, f32) -> Color32,
+) -> Response {
+    color_slider_2d_impl(ui, options, x_value, x_range, y_value, y_range, color_at, true)
+}
+
+/// How much the loupe of the 2D areas magnifies, and its size in points.
+const LOUPE_ZOOM: f32 = 8.0;
+const LOUPE_SIZE: f32 = 128.0;
+
+/// Fills `rect` with `color_at` over `x_range` × `y_range` (y pointing up), sampled on an
+/// `n`×`n` grid. See [`color_slider_2d_cells`] for `flat_cells`.
+fn paint_2d_fill(
+    painter: &Painter,
+    rect: Rect,
+    x_range: RangeInclusive<f32>,
+    y_range: RangeInclusive<f32>,
+    n: u32,
+    flat_cells: bool,
+    color_at: &impl Fn(f32, f32) -> Color32,
+) {
+    let n = n.max(1);
+    // Built fresh each frame: the painter takes ownership of the mesh and egui 0.14 never hands
+    // its buffers back, so keeping one in temp memory would only add a clone per frame.
+    let mut mesh = Mesh::default();
+    if flat_cells {
+        mesh.reserve_vertices(n as usize * n as usize * 4);
+        mesh.reserve_triangles(n as usize * n as usize * 2);
+    } else {
+        mesh.reserve_vertices((n as usize + 1) * (n as usize + 1));
+        mesh.reserve_triangles(n as usize * n as usize * 2);
+    }
+
+    let output = output_transform(painter.ctx());
+    let color_at = |x: f32, y: f32| output.apply(color_at(x, y));
+
+    profile_scope!("fill");
+    if flat_cells {
+        for xi in 0..n {
+            for yi in 0..n {
+                let xt = (xi as f32 + 0.5) / (n as f32);
+                let yt = (yi as f32 + 0.5) / (n as f32);
+                let color = color_at(egui::lerp(x_range.clone(), xt), egui::lerp(y_range.clone(), yt));
+                let cell = Rect::from_min_max(
+                    pos2(
+                        egui::lerp(rect.left()..=rect.right(), xi as f32 / (n as f32)),
+                        egui::lerp(rect.bottom()..=rect.top(), (yi + 1) as f32 / (n as f32)),
+                    ),
+                    pos2(
+                        egui::lerp(rect.left()..=rect.right(), (xi + 1) as f32 / (n as f32)),
+                        egui::lerp(rect.bottom()..=rect.top(), yi as f32 / (n as f32)),
+                    ),
+                );
+                mesh.add_colored_rect(cell, color);
+            }
+        }
+    }
+
+    for xi in (0..=n).filter(|_| !flat_cells) {
+        for yi in 0..=n {
+            let xt = xi as f32 / (n as f32);
+            let yt = yi as f32 / (n as f32);
+            let color = color_at(egui::lerp(x_range.clone(), xt), egui::lerp(y_range.clone(), yt));
+            let x = egui::lerp(rect.left()..=rect.right(), xt);
+            let y = egui::lerp(rect.bottom()..=rect.top(), yt);
+            mesh.colored_vertex(pos2(x, y), color);
+
+            if xi < n && yi < n {
+                let x_offset = 1;
+                let y_offset = n + 1;
+                let tl = yi * y_offset + xi;
+                mesh.add_triangle(tl, tl + x_offset, tl + y_offset);
+                mesh.add_triangle(tl + x_offset, tl + y_offset, tl + y_offset + x_offset);
+            }
+        }
+    }
+    painter.add(Shape::mesh(mesh));
+}
+
+fn color_slider_2d_impl(
+    ui: &mut Ui,
+    options: &PickerOptions,
+    x_value: &mut f32,
+    x_range: RangeInclusive<f32>,
+    y_value: &mut f32,
+    y_range: RangeInclusive<f32>,
+    color_at: impl Fn(f32, f32) -> Color32,
+    flat_cells: bool,
+) -> Response {
+    profile_function!();
+    let desired_size = Vec2::splat(options.layout.area_size.unwrap_or(ui.spacing().slider_width));
+    let (rect, response) = ui.allocate_at_least(desired_size, Sense::click_and_drag());
+
+    // On touch screens drag relatively: a finger landing next to the marker shouldn't make it
+    // jump there.
+    let t = vec2(
+        remap_clamp(*x_value, x_range.clone(), 0.0..=1.0),
+        remap_clamp(*y_value, y_range.clone(), 0.0..=1.0),
+    );
+    if let Some(t) = slider_drag(ui, &response, rect, t, options, options.is_touch(ui), true) {
+        *x_value = egui::lerp(x_range.clone(), t.x);
+        *y_value = egui::lerp(y_range.clone(), t.y);
+    }
+
+    let visuals = ui.style().interact(&response);
+    let n = options.render_quality(ui).subdivisions_2d;
+    paint_2d_fill(ui.painter(), rect, x_range.clone(), y_range.clone(), n, flat_cells, &color_at);
+
+    ui.painter().rect_stroke(rect, 0.0, visuals.bg_stroke); // outline
+
+    // Show where the slider is at:
+    let x = egui::lerp(rect.left()..=rect.right(), remap_clamp(*x_value, x_range.clone(), 0.0..=1.0));
+    let y = egui::lerp(rect.bottom()..=rect.top(), remap_clamp(*y_value, y_range.clone(), 0.0..=1.0));
+    // Only the marker is painted transformed here; `show_color` in the drag tooltip does its own.
+    let picked_color = color_at(*x_value, *y_value);
+    let marker_color = output_transform(ui.ctx()).apply(picked_color);
+    let marker = &options.marker;
+    let r = rect.width().min(rect.height()) / 12.0 * marker.size;
+    let stroke = marker.stroke(visuals.fg_stroke.width, marker_color);
+    let center = pos2(x, y);
+    match marker.shape_2d {
+        MarkerShape::Triangle => {
+            ui.painter().add(Shape::polygon(
+                vec![center, pos2(x + r, y + 2.0 * r), pos2(x - r, y + 2.0 * r)],
+                marker_color,
+                stroke,
+            ));
+        }
+        MarkerShape::Circle => ui.painter().circle(center, r, marker_color, stroke),
+        MarkerShape::Bar => {
+            // Crosshair, leaving the picked point itself visible.
+            for dir in &[vec2(1.0, 0.0), vec2(-1.0, 0.0), vec2(0.0, 1.0), vec2(0.0, -1.0)] {
+                ui.painter().line_segment([center + *dir * (r * 0.5), center + *dir * (r * 2.0)], stroke);
+            }
+        }
+    }
+
+    let loupe_pos = response.hover_pos().filter(|_| options.loupe_held(ui));
+    if let Some(pos) = loupe_pos {
+        // Resampled rather than scaled up, so that subtle differences (e.g. of chroma near the
+        // neutral axis) show up. The window stays inside the area, so the pointer may be off
+        // center near the edges.
+        let pointer_t = vec2(
+            remap_clamp(pos.x, rect.left()..=rect.right(), 0.0..=1.0),
+            remap_clamp(pos.y, rect.bottom()..=rect.top(), 0.0..=1.0),
+        );
+        let half = 0.5 / LOUPE_ZOOM;
+        let window = |range: &RangeInclusive<f32>, t: f32| {
+            let center = t.clamp(half, 1.0 - half);
+            (egui::lerp(range.clone(), center - half)..=egui::lerp(range.clone(), center + half), center)
+        };
+        let (zoomed_x, center_x) = window(&x_range, pointer_t.x);
+        let (zoomed_y, center_y) = window(&y_range, pointer_t.y);
+        let hovered_color = color_at(
+            egui::lerp(x_range.clone(), pointer_t.x),
+            egui::lerp(y_range.clone(), pointer_t.y),
+        );
+        egui::show_tooltip_at_pointer(ui.ctx(), response.id.with("loupe"), |ui| {
+            let (loupe_rect, _) = ui.allocate_exact_size(Vec2::splat(LOUPE_SIZE), Sense::hover());
+            paint_2d_fill(ui.painter(), loupe_rect, zoomed_x, zoomed_y, n, flat_cells, &color_at);
+            let at = pos2(
+                egui::lerp(loupe_rect.left()..=loupe_rect.right(), 0.5 + (pointer_t.x - center_x) * LOUPE_ZOOM),
+                egui::lerp(loupe_rect.bottom()..=loupe_rect.top(), 0.5 + (pointer_t.y - center_y) * LOUPE_ZOOM),
+            );
+            let crosshair =
+                contrast::most_contrasting(hovered_color, &[Color32::BLACK, Color32::WHITE], ContrastMetric::Wcag2);
+            let stroke = Stroke::new(1.0, crosshair);
+            for dir in &[vec2(1.0, 0.0), vec2(-1.0, 0.0), vec2(0.0, 1.0), vec2(0.0, -1.0)] {
+                ui.painter().line_segment([at + *dir * 3.0, at + *dir * 9.0], stroke);
+            }
+            let [r, g, b, a] = hovered_color.to_array();
+            ui.monospace(convert::to_hex(convert::srgb8_to_oklch([r, g, b, a])));
+        });
+    } else if response.dragged() {
+        // The pointer (or finger) covers the marker while dragging, so show the color beside it.
+        egui::show_tooltip_at_pointer(ui.ctx(), response.id.with("drag_preview"), |ui| {
+            let size = Vec2::splat(ui.spacing().interact_size.y * 3.0);
+            show_color(ui, picked_color, size, options.swatch);
+            let [r, g, b, a] = picked_color.to_array();
+            ui.monospace(convert::to_hex(convert::srgb8_to_oklch([r, g, b, a])));
+        });
+    }
+
+    response
+}
+
+/// Ticks along the bottom of a 1D slider at multiples of `step`, if not too dense to see.
+pub(crate) fn paint_snap_ticks(ui: &Ui, rect: Rect, range: RangeInclusive<f32>, step: Option<f32>) {
+    let step = match step {
+        Some(step) if step > 0.0 => step,
+        _ => return,
+    };
+    let (min, max) = (*range.start(), *range.end());
+    if (max - min) / step > rect.width() / 3.0 {
+        return;
+    }
+    let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+    let mut value = (min / step).ceil() * step;
+    while value <= max {
+        let x = egui::lerp(rect.left()..=rect.right(), remap_clamp(value, range.clone(), 0.0..=1.0));
+        ui.painter().line_segment(
+            [pos2(x, rect.bottom() - rect.height() / 4.0), pos2(x, rect.bottom())],
+            stroke,
+        );
+        value += step;
+    }
+}
+
+/// Edits the hue `h` (in radians) in `unit`.
+pub(crate) fn hue_drag_value(ui: &mut Ui, unit: HueUnit, format: &NumberFormat, h: &mut f32) -> Response {
+    let decimals = match unit {
+        HueUnit::Degrees => format.scaled_decimals(),
+        HueUnit::Radians | HueUnit::Turns => format.decimals,
+    };
+    let mut value = unit.from_radians(*h);
+    let response = ui.add(
+        DragValue::new(&mut value)
+            .speed(unit.full_turn() / 360.0)
+            .clamp_range(0.0..=unit.full_turn())
+            .fixed_decimals(decimals)
+            .suffix(unit.suffix()),
+    );
+    // Only write back edits: the round trip through `unit` isn't exact.
+    if response.changed() {
+        *h = unit.to_radians(value);
+    }
+    response
+}
+
+/// Edits a lightness or alpha `value` in `0..=1`, as a percentage if `format` says so.
+pub(crate) fn unit_interval_drag_value(ui: &mut Ui, format: &NumberFormat, value: &mut f32) -> Response {
+    if format.percent {
+        let mut percent = *value * 100.0;
+        let response = ui.add(
+            DragValue::new(&mut percent)
+                .speed(0.5)
+                .clamp_range(0.0..=100.0)
+                .fixed_decimals(format.scaled_decimals())
+                .suffix("%"),
+        );
+        if response.changed() {
+            *value = percent / 100.0;
+        }
+        response
+    } else {
+        ui.add(
+            DragValue::new(value)
+                .speed(0.005)
+                .clamp_range(0.0..=1.0)
+                .fixed_decimals(format.decimals),
+        )
+    }
+}
+
+/// Marks the ends of `range` on a lightness slider, past which the color leaves the gamut and
+/// the slider shows it clipped.
+pub(crate) fn paint_lightness_gamut_ticks(ui: &Ui, rect: Rect, range: RangeInclusive<f32>) {
+    let stroke = Stroke::new(2.0, ui.visuals().widgets.noninteractive.fg_stroke.color);
+    let tick = rect.height() / 3.0;
+    for &l in &[*range.start(), *range.end()] {
+        if l <= 0.0 || l >= 1.0 {
+            continue;
+        }
+        let x = egui::lerp(rect.left()..=rect.right(), l);
+        ui.painter().line_segment([pos2(x, rect.top()), pos2(x, rect.top() + tick)], stroke);
+        ui.painter().line_segment([pos2(x, rect.bottom() - tick), pos2(x, rect.bottom())], stroke);
+    }
+}
+
+/// Spacing in Oklab of the lines drawn by [`paint_delta_e_grid`] and [`paint_hue_chroma_grid`].
+/// Oklab is close to perceptually uniform, so this is roughly a constant ΔE.
+const GRID_STEP: f32 = 0.1;
+
+fn grid_stroke(ui: &Ui) -> Stroke {
+    let color = ui.visuals().widgets.noninteractive.fg_stroke.color;
+    Stroke::new(1.0, Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), 96))
+}
+
+/// Draws lines of constant x and y at multiples of [`GRID_STEP`] over a 2D area whose axes are
+/// Oklab distances, such as chroma × lightness.
+pub(crate) fn paint_delta_e_grid(ui: &Ui, rect: Rect, x_range: RangeInclusive<f32>, y_range: RangeInclusive<f32>) {
+    let painter = ui.painter_at(rect);
+    let stroke = grid_stroke(ui);
+    let mut x = (x_range.start() / GRID_STEP).floor() * GRID_STEP + GRID_STEP;
+    while x < *x_range.end() {
+        let sx = egui::lerp(rect.left()..=rect.right(), remap_clamp(x, x_range.clone(), 0.0..=1.0));
+        painter.line_segment([pos2(sx, rect.top()), pos2(sx, rect.bottom())], stroke);
+        x += GRID_STEP;
+    }
+    let mut y = (y_range.start() / GRID_STEP).floor() * GRID_STEP + GRID_STEP;
+    while y < *y_range.end() {
+        let sy = egui::lerp(rect.bottom()..=rect.top(), remap_clamp(y, y_range.clone(), 0.0..=1.0));
+        painter.line_segment([pos2(rect.left(), sy), pos2(rect.right(), sy)], stroke);
+        y += GRID_STEP;
+    }
+}
+
+/// Draws circles of constant chroma and rays of constant hue (every 30°) over an a × b area
+/// spanning `-extent..=extent` on both axes.
+pub(crate) fn paint_hue_chroma_grid(ui: &Ui, rect: Rect, extent: f32) {
+    let painter = ui.painter_at(rect);
+    let stroke = grid_stroke(ui);
+    let scale = rect.width() / (2.0 * extent);
+    let center = rect.center();
+    let mut c = GRID_STEP;
+    while c <= extent * std::f32::consts::SQRT_2 {
+        painter.circle_stroke(center, c * scale, stroke);
+        c += GRID_STEP;
+    }
+    for i in 0..12 {
+        let h = (i as f32 * 30.0).to_radians();
+        // y is flipped: b points up.
+        let dir = vec2(h.cos(), -h.sin());
+        painter.line_segment([center, center + dir * rect.width()], stroke);
+    }
+}
+
+/// Dims the cells of an `n`×`n` grid over a 2D area (y pointing up) whose centers aren't
+/// `allowed`, see [`ColorConstraint`](crate::ColorConstraint).
+pub(crate) fn paint_disallowed(
+    ui: &Ui,
+    rect: Rect,
+    x_range: RangeInclusive<f32>,
+    y_range: RangeInclusive<f32>,
+    n: u32,
+    allowed: impl Fn(f32, f32) -> bool,
+) {
+    let n = n.max(1);
+    let painter = ui.painter_at(rect);
+    let cell = rect.size() / n as f32;
+    for yi in 0..n {
+        for xi in 0..n {
+            let (tx, ty) = ((xi as f32 + 0.5) / n as f32, (yi as f32 + 0.5) / n as f32);
+            if !allowed(egui::lerp(x_range.clone(), tx), egui::lerp(y_range.clone(), ty)) {
+                let min = pos2(rect.left() + xi as f32 * cell.x, rect.bottom() - (yi + 1) as f32 * cell.y);
+                painter.rect_filled(Rect::from_min_size(min, cell), 0.0, Color32::from_black_alpha(160));
+            }
+        }
+    }
+}
+
+/// Draws the sRGB gamut boundary at hue `h` (the maximum chroma at each lightness) over a
+/// chroma × lightness area.
+pub(crate) fn paint_gamut_boundary(ui: &Ui, rect: Rect, h: f32, chroma_range: RangeInclusive<f32>, n: u32) {
+    let n = n.max(1);
+    let points = (0..=2 * n)
+        .map(|i| {
+            let l = i as f32 / (2 * n) as f32;
+            let c = convert::max_chroma_for(l, h);
+            pos2(
+                egui::lerp(rect.left()..=rect.right(), remap_clamp(c, chroma_range.clone(), 0.0..=1.0)),
+                egui::lerp(rect.bottom()..=rect.top(), l),
+            )
+        })
+        .collect();
+    let visuals = &ui.visuals().widgets.noninteractive;
+    ui.painter_at(rect).add(Shape::line(points, Stroke::new(1.5, visuals.fg_stroke.color)));
+}
@@ -0,0 +1,73 @@
+//! Snapping picked colors to discrete steps, e.g. for pixel art and design tokens.
+
+use colstodian::*;
+
+use crate::{convert, EncodedSrgbA, OklchA};
+
+/// Steps to snap picked colors to. Each `Some(step)` snaps that Oklch channel to multiples
+/// of `step`; the default snaps nothing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Snap {
+    pub lightness: Option<f32>,
+    pub chroma: Option<f32>,
+    /// In radians, e.g. `15f32.to_radians()`.
+    pub hue: Option<f32>,
+    pub alpha: Option<f32>,
+    /// After the above, snap each encoded sRGB channel to multiples of this many 8-bit levels:
+    /// `Some(1)` gives exactly representable `Color32`s, `Some(51)` web-safe colors.
+    pub srgb_step: Option<u8>,
+    /// Draw the snap steps as ticks on the Oklch sliders.
+    pub show_grid: bool,
+}
+
+fn snap(value: f32, step: Option<f32>) -> f32 {
+    match step {
+        Some(step) if step > 0.0 => (value / step).round() * step,
+        _ => value,
+    }
+}
+
+impl Snap {
+    /// Snap to colors exactly representable as 8-bit sRGB.
+    pub fn srgb8() -> Self {
+        Self {
+            srgb_step: Some(1),
+            ..Default::default()
+        }
+    }
+
+    /// Snap to the 216 web-safe colors.
+    pub fn web_safe() -> Self {
+        Self {
+            srgb_step: Some(51),
+            ..Default::default()
+        }
+    }
+
+    pub fn apply(&self, color: OklchA) -> OklchA {
+        let mut snapped = color;
+        snapped.col.l = snap(color.col.l, self.lightness).clamp(0.0, 1.0);
+        snapped.col.c = snap(color.col.c, self.chroma).max(0.0);
+        snapped.col.h = convert::wrap_hue(snap(color.col.h, self.hue));
+        snapped.alpha = snap(color.alpha, self.alpha).clamp(0.0, 1.0);
+
+        if let Some(step) = self.srgb_step.filter(|&step| step > 0) {
+            let step = step as f32 / 255.0;
+            let srgb: EncodedSrgbA = snapped.convert();
+            let srgb = srgb.saturate();
+            let hue = snapped.col.h;
+            snapped = EncodedSrgbA::new(
+                snap(srgb.col.r, Some(step)),
+                snap(srgb.col.g, Some(step)),
+                snap(srgb.col.b, Some(step)),
+                snapped.alpha,
+            )
+            .convert();
+            // Grays have no hue of their own: keep the one we had so the hue slider stays put.
+            if snapped.col.c < 1e-4 {
+                snapped.col.h = hue;
+            }
+        }
+        snapped
+    }
+}
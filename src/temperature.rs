@@ -0,0 +1,95 @@
+//! Correlated color temperature and tint, via the Planckian locus in CIE 1960 UCS.
+
+use colstodian::*;
+
+use crate::cielab::{mul, LINEAR_SRGB_TO_XYZ_D65, XYZ_D65_TO_LINEAR_SRGB};
+use crate::{LinearSrgbA, OklchA, Srgba};
+
+/// The range of temperatures (in Kelvin) the approximations below are valid over.
+pub const TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 1000.0..=12000.0;
+
+/// Planckian locus in CIE 1960 (u, v), after Krystek (1985).
+fn planckian_uv(kelvin: f32) -> [f32; 2] {
+    let t = kelvin as f64;
+    let u = (0.860_117_757 + 1.541_182_54e-4 * t + 1.286_412_12e-7 * t * t)
+        / (1.0 + 8.424_202_35e-4 * t + 7.081_451_63e-7 * t * t);
+    let v = (0.317_398_726 + 4.228_062_45e-5 * t + 4.204_816_91e-8 * t * t)
+        / (1.0 - 2.897_418_16e-5 * t + 1.614_560_53e-7 * t * t);
+    [u as f32, v as f32]
+}
+
+/// Unit normal to the locus at `kelvin`, pointing towards green (positive Duv).
+fn planckian_normal(kelvin: f32) -> [f32; 2] {
+    let [u0, v0] = planckian_uv(kelvin - 1.0);
+    let [u1, v1] = planckian_uv(kelvin + 1.0);
+    let (du, dv) = (u1 - u0, v1 - v0);
+    let len = du.hypot(dv);
+    let normal = [-dv / len, du / len];
+    if normal[1] < 0.0 {
+        [-normal[0], -normal[1]]
+    } else {
+        normal
+    }
+}
+
+/// Linear sRGB of a light at `kelvin`, offset along the green–magenta axis by `tint`
+/// (in Duv units, positive towards magenta), normalized so its largest component is 1.
+pub fn linear_srgb_from_temperature(kelvin: f32, tint: f32) -> [f32; 3] {
+    let [u, v] = planckian_uv(kelvin);
+    let [nu, nv] = planckian_normal(kelvin);
+    let (u, v) = (u - tint * nu, v - tint * nv);
+
+    let denom = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / denom, 2.0 * v / denom);
+    let xyz = [x / y, 1.0, (1.0 - x - y) / y];
+
+    let rgb = mul(XYZ_D65_TO_LINEAR_SRGB, xyz);
+    let max = rgb[0].max(rgb[1]).max(rgb[2]);
+    [rgb[0] / max, rgb[1] / max, rgb[2] / max]
+}
+
+/// Approximate inverse of [`linear_srgb_from_temperature`], returning `(kelvin, tint)`.
+/// Temperature is estimated with McCamy's formula and clamped to [`TEMPERATURE_RANGE`].
+pub fn temperature_from_linear_srgb(rgb: [f32; 3]) -> (f32, f32) {
+    let [x, y, z] = mul(LINEAR_SRGB_TO_XYZ_D65, rgb);
+    let sum = x + y + z;
+    if sum <= 0.0 {
+        return (6500.0, 0.0);
+    }
+    let (cx, cy) = (x / sum, y / sum);
+    let n = (cx - 0.3320) / (0.1858 - cy);
+    let kelvin = (449.0 * n * n * n + 3525.0 * n * n + 6823.3 * n + 5520.33)
+        .clamp(*TEMPERATURE_RANGE.start(), *TEMPERATURE_RANGE.end());
+
+    let denom = -2.0 * cx + 12.0 * cy + 3.0;
+    let (u, v) = (4.0 * cx / denom, 6.0 * cy / denom);
+    let [u0, v0] = planckian_uv(kelvin);
+    let [nu, nv] = planckian_normal(kelvin);
+    let duv = (u - u0) * nu + (v - v0) * nv;
+    (kelvin, -duv)
+}
+
+/// `[kelvin, tint, lightness]`, where lightness is roughly Oklab L.
+pub(crate) fn oklch_to_temperature(color: &OklchA) -> [f32; 3] {
+    let lin: LinearSrgbA = color.convert();
+    let rgb = [lin.col.r, lin.col.g, lin.col.b];
+    let (kelvin, tint) = temperature_from_linear_srgb(rgb);
+    let max = rgb[0].max(rgb[1]).max(rgb[2]).max(0.0);
+    [kelvin, tint, max.cbrt()]
+}
+
+fn temperature_to_linear([kelvin, tint, lightness]: [f32; 3], alpha: f32) -> LinearSrgbA {
+    // Scaling linear light keeps the chromaticity exact; cubing makes the slider
+    // roughly perceptually uniform, like Oklab L.
+    let scale = lightness * lightness * lightness;
+    let [r, g, b] = linear_srgb_from_temperature(kelvin, tint);
+    LinearSrgbA::new(r * scale, g * scale, b * scale, alpha)
+}
+
+pub(crate) fn temperature_to_oklch(coords: [f32; 3], alpha: f32) -> OklchA {
+    temperature_to_linear(coords, alpha).convert()
+}
+
+pub(crate) fn temperature_to_srgba(coords: [f32; 3], alpha: f32) -> Srgba {
+    temperature_to_linear(coords, alpha).convert()
+}
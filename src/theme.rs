@@ -0,0 +1,65 @@
+//! Derive a full egui [`Visuals`] from a single seed color.
+//!
+//! All colors share the seed's hue. Lightness is stepped in Oklab so that the difference between
+//! e.g. inactive and hovered widgets looks the same whatever the seed, and chroma is kept low for
+//! large surfaces and clipped to the sRGB gamut everywhere.
+
+use egui::{Color32, Stroke, Visuals};
+
+use crate::convert::{max_chroma_for, oklch_to_srgb8};
+use crate::OklchA;
+
+/// The seed color at lightness `l`, with chroma at most `max_c` (and within gamut).
+fn tone(seed: &OklchA, l: f32, max_c: f32) -> Color32 {
+    let h = seed.col.h;
+    let c = seed.col.c.min(max_c).min(max_chroma_for(l, h));
+    let [r, g, b, a] = oklch_to_srgb8(OklchA::new(l, c, h, 1.0));
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+/// Builds dark or light [`Visuals`] tinted by `seed`. Fields not related to color (rounding,
+/// shadows, spacing...) are taken from egui's default dark or light visuals.
+pub fn visuals_from_seed(seed: OklchA, dark_mode: bool) -> Visuals {
+    // Lightness as seen from the "background" end: flipped for light mode.
+    let l = |l: f32| if dark_mode { l } else { 1.0 - l };
+    let surface = |lightness: f32| tone(&seed, l(lightness), 0.02);
+    let widget = |lightness: f32| tone(&seed, l(lightness), 0.05);
+    let text = |lightness: f32| tone(&seed, l(lightness), 0.02);
+
+    let mut visuals = if dark_mode {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    };
+
+    visuals.extreme_bg_color = surface(0.12);
+    visuals.code_bg_color = surface(0.18);
+    visuals.faint_bg_color = surface(0.25);
+
+    let widgets = &mut visuals.widgets;
+    widgets.noninteractive.bg_fill = surface(0.22); // also the window fill
+    widgets.noninteractive.bg_stroke = Stroke::new(1.0, surface(0.32));
+    widgets.noninteractive.fg_stroke = Stroke::new(1.0, text(0.82));
+
+    widgets.inactive.bg_fill = widget(0.32);
+    widgets.inactive.fg_stroke = Stroke::new(1.0, text(0.86));
+
+    widgets.hovered.bg_fill = widget(0.40);
+    widgets.hovered.bg_stroke = Stroke::new(1.0, widget(0.55));
+    widgets.hovered.fg_stroke = Stroke::new(1.5, text(0.94));
+
+    widgets.active.bg_fill = widget(0.48);
+    widgets.active.bg_stroke = Stroke::new(1.0, text(0.94));
+    widgets.active.fg_stroke = Stroke::new(2.0, text(1.0));
+
+    widgets.open.bg_fill = widget(0.28);
+    widgets.open.bg_stroke = Stroke::new(1.0, widget(0.45));
+    widgets.open.fg_stroke = Stroke::new(1.0, text(0.86));
+
+    // Accents keep more of the seed's own chroma.
+    visuals.selection.bg_fill = tone(&seed, l(0.45), f32::INFINITY);
+    visuals.selection.stroke = Stroke::new(1.0, tone(&seed, l(0.85), 0.1));
+    visuals.hyperlink_color = tone(&seed, l(0.75), f32::INFINITY);
+
+    visuals
+}
@@ -0,0 +1,97 @@
+//! Color buttons bound to named design tokens, with a local override.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use crate::{show_color, ColorEditButton, OklchA, Palette, PickerControl, Srgba, SwatchStyle};
+
+/// Where [`token_color_edit`] looks up token values, e.g. the app's theme.
+pub trait TokenSource {
+    /// The value of the token `name`, or `None` if there is no such token.
+    fn token(&self, name: &str) -> Option<OklchA>;
+}
+
+/// The entries of a palette as tokens, by name.
+impl TokenSource for Palette {
+    fn token(&self, name: &str) -> Option<OklchA> {
+        self.entries.iter().find(|entry| entry.name == name).map(|entry| entry.color)
+    }
+}
+
+impl<S: BuildHasher> TokenSource for HashMap<String, OklchA, S> {
+    fn token(&self, name: &str) -> Option<OklchA> {
+        self.get(name).copied()
+    }
+}
+
+/// The token a picker is bound to, shown above its controls.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TokenBinding {
+    pub name: String,
+    /// `None` if the token doesn't exist.
+    pub value: Option<OklchA>,
+}
+
+/// Shows the token's value next to the picked color, with a button reverting to the token.
+/// Returns whether it did.
+pub(crate) fn token_ui(ui: &mut Ui, color: &mut OklchA, token: &TokenBinding, style: SwatchStyle) -> bool {
+    let to_color32 = |c: OklchA| -> Color32 { c.convert::<Srgba>().saturate().into_cint().into() };
+    let mut reverted = false;
+    ui.horizontal(|ui| {
+        ui.label(format!("Token {}", token.name));
+        match token.value {
+            Some(value) => {
+                let size = Vec2::splat(ui.spacing().interact_size.y);
+                show_color(ui, to_color32(value), size, style).on_hover_text("Token value");
+                ui.label("→");
+                show_color(ui, to_color32(*color), size, style).on_hover_text("Local value");
+                let overridden = *color != value;
+                if ui.add_enabled(overridden, Button::new("Revert to token")).clicked() {
+                    *color = value;
+                    reverted = true;
+                }
+            }
+            None => {
+                ui.label("(unknown token)");
+            }
+        }
+    });
+    reverted
+}
+
+/// A color button for the design token `name` in `tokens`, labeled with the name. `local` is
+/// the override of the token's value, `None` to use the token as is; editing the color sets
+/// it, and "Revert to token" in the popup clears it.
+///
+/// ```ignore
+/// token_color_edit(ui, &theme, "accent", &mut overrides.accent);
+/// let accent = overrides.accent.or_else(|| theme.token("accent"));
+/// ```
+pub fn token_color_edit(ui: &mut Ui, tokens: &dyn TokenSource, name: &str, local: &mut Option<OklchA>) -> Response {
+    let value = tokens.token(name);
+    // Gray stands in for unknown tokens.
+    let mut color = local.or(value).unwrap_or_else(|| OklchA::new(0.5, 0.0, 0.0, 1.0));
+    let binding = TokenBinding {
+        name: name.to_owned(),
+        value,
+    };
+    ui.horizontal(|ui| {
+        let picker = ColorEditButton::new(&mut color).token(binding).show(ui);
+        if picker.changed_control() == Some(PickerControl::Token) {
+            *local = None;
+        } else if picker.changed() {
+            *local = Some(color);
+        }
+        let label = if local.is_some() {
+            ui.add(Label::new(name).italics()).on_hover_text("Overrides the token")
+        } else {
+            ui.label(name)
+        };
+        picker.response.union(label)
+    })
+    .inner
+}
@@ -0,0 +1,423 @@
+//! Color edit buttons and widgets for egui's and other crates' color types.
+
+use egui::*;
+
+use cint::ColorInterop;
+
+use crate::cache::{Cache, CACHE_MAX_AGE_FRAMES};
+use crate::picker::{
+    button_tooltip, color_edit_popup, color_picker_oklab_2d, is_picker_shown, offset_oklch, oklch_delta, ButtonTooltip,
+    ColorEditButton, PickerOptions, PopupLayout,
+};
+use crate::{
+    color_button, compat, convert, gradient_edit, paint_swatch, AlphaDisplay, Controls, Gradient, LinearSrgbA, OklabA,
+    OklchA, Srgba, SwatchStyle,
+};
+
+pub fn color_edit_button_oklch(ui: &mut Ui, color: &mut OklchA) -> Response {
+    ColorEditButton::new(color).show(ui).response
+}
+
+/// Like [`color_edit_button_oklch`], but edits the rectangular Oklab `a` and `b`
+/// coordinates directly with a 2D a×b plane at fixed lightness.
+pub fn color_edit_button_oklab(ui: &mut Ui, color: &mut OklabA) -> Response {
+    let col_srgba: Srgba = color.convert();
+    let style = SwatchStyle::default();
+    let button_response = color_button(ui, col_srgba.into_cint().into(), style);
+    let popup_id = button_response.id.with("popup");
+    let response = color_edit_popup(ui, button_response, popup_id, PopupLayout::default(), true, |ui| {
+        color_picker_oklab_2d(ui, color, col_srgba, &PickerOptions::default())
+    });
+    let color = *color;
+    button_tooltip(response, ButtonTooltip::default(), || color.convert(), None, style)
+}
+
+/// The unquantized color of an open [`color_edit_button`] (or [`color_edit_button_rgba`]), and
+/// the value it was last written to the caller's color as.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EditSession<T> {
+    pub(crate) oklch: OklchA,
+    pub(crate) applied: T,
+}
+
+fn color_edit_button_inner(
+    ui: &mut Ui,
+    color: &mut Srgba,
+    button_response: Response,
+    tooltip: ButtonTooltip,
+) -> Response {
+    // While the picker is open, keep editing the float color rather than converting the
+    // quantized one back every frame, which drifts (most visibly for dark colors). It is only
+    // dropped if the color was changed from outside.
+    let session_id = button_response.id.with("session");
+    let popup_id = button_response.id.with("popup");
+    let session = temp_get!(ui, session_id, EditSession<[u8; 4]>).filter(|session| session.applied == color.to_u8());
+
+    // To ensure we keep hue slider when `color` is gray we store the
+    // full Oklch color in a cache:
+
+    let cached = ui
+        .ctx()
+        .memory()
+        .data_temp
+        .get_or_default::<Cache<[u8; 4], OklchA>>()
+        .get(&color.to_u8())
+        .cloned();
+    // ...and, with the `persistence` feature, per widget in egui's persisted memory, so that it
+    // survives restarts of the app.
+    #[cfg(feature = "persistence")]
+    let persisted_id = button_response.id.with("oklch");
+    #[cfg(feature = "persistence")]
+    let cached = cached.or_else(|| {
+        let (srgb, [l, c, h, alpha]) = persisted_get!(ui.ctx(), persisted_id, ([u8; 4], [f32; 4]))?;
+        // Only if the color wasn't changed by something else since.
+        if srgb == color.to_u8() {
+            Some(OklchA::new(l, c, h, alpha))
+        } else {
+            None
+        }
+    });
+    let mut oklch = session.map(|session| session.oklch).or(cached).unwrap_or_else(|| color.convert());
+
+    let response = ColorEditButton::new(&mut oklch)
+        .tooltip(tooltip)
+        .show_with_button(ui, button_response)
+        .response;
+
+    // Only write (and so quantize) the color when it was actually edited.
+    if response.changed() {
+        *color = oklch.convert();
+    }
+    if is_picker_shown(ui, popup_id) {
+        temp_insert!(ui, session_id, EditSession { oklch, applied: color.to_u8() });
+    } else {
+        temp_remove!(ui, session_id, EditSession<[u8; 4]>);
+    }
+
+    let frame = compat::frame_nr(ui.ctx());
+    let mut memory = ui.ctx().memory();
+    let cache = memory.data_temp.get_mut_or_default::<Cache<[u8; 4], OklchA>>();
+    cache.set(color.to_u8(), oklch, frame);
+    cache.gc(frame, CACHE_MAX_AGE_FRAMES);
+    drop(memory);
+
+    #[cfg(feature = "persistence")]
+    persisted_insert!(
+        ui.ctx(),
+        persisted_id,
+        (color.to_u8(), [oklch.col.l, oklch.col.c, oklch.col.h, oklch.alpha])
+    );
+
+    response
+}
+
+/// State of a [`color_edit_button_multi`]: the colors as they were when editing started and the
+/// picker color they are offset relative to, so that quantizing to `Color32` every frame doesn't
+/// accumulate drift.
+#[derive(Clone)]
+struct MultiEditState {
+    base: Vec<OklchA>,
+    reference: OklchA,
+    current: OklchA,
+    applied: Vec<Color32>,
+}
+
+/// Shows one button editing all of `colors` together. The picker starts out at the first
+/// color, and its changes are applied to every color as relative Oklch offsets, e.g. to retint
+/// a group of palette entries while keeping their differences.
+pub fn color_edit_button_multi(ui: &mut Ui, colors: &mut [&mut Color32]) -> Response {
+    let current_colors: Vec<Color32> = colors.iter().map(|color| **color).collect();
+    // The first color is the picker color as of the last edit, quantized as the button shows it.
+    let first = current_colors.first().copied().unwrap_or(Color32::GRAY);
+    let button_response = color_button(ui, first, SwatchStyle::default());
+    let state_id = button_response.id.with("multi_edit");
+
+    let cached = temp_get!(ui, state_id, MultiEditState);
+    let mut state = match cached {
+        Some(state) if state.applied == current_colors => state,
+        _ => {
+            // Colors changed from outside (or this is the first frame): start a new edit.
+            let base: Vec<OklchA> = current_colors
+                .iter()
+                .map(|color| Srgba::from(color.into_cint()).convert())
+                .collect();
+            let reference = base.first().copied().unwrap_or_else(|| OklchA::new(0.5, 0.0, 0.0, 1.0));
+            MultiEditState {
+                base,
+                reference,
+                current: reference,
+                applied: current_colors,
+            }
+        }
+    };
+
+    let response = ColorEditButton::new(&mut state.current)
+        .show_with_button(ui, button_response)
+        .response
+        .on_hover_text(format!("Editing {} colors", colors.len()));
+
+    if response.changed() {
+        for (color, base) in colors.iter_mut().zip(&state.base) {
+            let edited = offset_oklch(*base, oklch_delta(&state.reference, &state.current));
+            **color = Color32::from_cint(edited.convert::<Srgba>().saturate().to_u8().into());
+        }
+        state.applied = colors.iter().map(|color| **color).collect();
+    }
+    temp_insert!(ui, state_id, state);
+
+    response
+}
+
+/// Shows a button with the given color.
+/// If the user clicks the button, a full color picker is shown.
+pub fn color_edit_button(ui: &mut Ui, color: &mut Color32) -> Response {
+    // Most buttons are closed most of the time, so skip the conversions and the cache lookup
+    // unless the picker is showing. This must match what `ColorEditButton` does when closed.
+    let button_response = color_button(ui, *color, SwatchStyle::default());
+    let popup_id = button_response.id.with("popup");
+    if !is_picker_shown(ui, popup_id) {
+        let color32 = *color;
+        let response = button_tooltip(
+            button_response,
+            ButtonTooltip::default(),
+            || Srgba::from(color32.into_cint()).convert(),
+            None,
+            SwatchStyle::default(),
+        );
+        if response.clicked() {
+            compat::open_popup(ui.ctx(), popup_id);
+        }
+        return response;
+    }
+
+    let mut col = Srgba::from(color.into_cint());
+
+    let res = color_edit_button_inner(ui, &mut col, button_response, ButtonTooltip::default());
+
+    *color = Color32::from_cint(col.to_u8().into());
+
+    res
+}
+
+/// Like [`color_edit_button`], for colors stored as linear, premultiplied float [`Rgba`]. These
+/// are read and written without ever being quantized to 8 bits. `alpha` works as in egui's own
+/// [`color_picker::color_edit_button_rgba`], except that the picker can't make additive colors,
+/// so [`Alpha::BlendOrAdditive`](color_picker::Alpha::BlendOrAdditive) is the same as
+/// [`Alpha::OnlyBlend`](color_picker::Alpha::OnlyBlend).
+pub fn color_edit_button_rgba(ui: &mut Ui, rgba: &mut Rgba, alpha: color_picker::Alpha) -> Response {
+    // Opaque colors get no alpha slider, and are shown without alpha, as in egui's own editor.
+    let opaque = alpha == color_picker::Alpha::Opaque;
+    let (controls, alpha_display) = if opaque {
+        (Controls { alpha: false, ..Controls::ALL }, AlphaDisplay::OpaqueOnly)
+    } else {
+        (Controls::ALL, AlphaDisplay::default())
+    };
+    let style = SwatchStyle {
+        alpha: alpha_display,
+        ..SwatchStyle::default()
+    };
+    let button_response = color_button(ui, Color32::from(*rgba), style);
+    let session_id = button_response.id.with("session");
+    let popup_id = button_response.id.with("popup");
+
+    let mut oklch = match temp_get!(ui, session_id, EditSession<Rgba>) {
+        Some(session) if session.applied == *rgba => session.oklch,
+        _ if rgba.a() > 0.0 => {
+            let a = rgba.a();
+            LinearSrgbA::new(rgba.r() / a, rgba.g() / a, rgba.b() / a, a).convert()
+        }
+        _ => OklchA::new(0.0, 0.0, 0.0, 0.0),
+    };
+
+    let response = ColorEditButton::new(&mut oklch)
+        .controls(controls)
+        .alpha_display(alpha_display)
+        .show_with_button(ui, button_response)
+        .response;
+
+    if response.changed() {
+        // Whatever alpha the color had, e.g. from a palette entry picked in the popup.
+        if opaque {
+            oklch.alpha = 1.0;
+        }
+        let lin: LinearSrgbA = oklch.convert();
+        let a = lin.alpha.clamp(0.0, 1.0);
+        let channel = |v: f32| v.clamp(0.0, 1.0) * a;
+        *rgba = Rgba::from_rgba_premultiplied(channel(lin.col.r), channel(lin.col.g), channel(lin.col.b), a);
+    }
+    if is_picker_shown(ui, popup_id) {
+        temp_insert!(ui, session_id, EditSession { oklch, applied: *rgba });
+    } else {
+        temp_remove!(ui, session_id, EditSession<Rgba>);
+    }
+
+    response
+}
+
+/// Which swatch of a [`stroke_fill_edit`] its popup edits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StrokeFillTarget {
+    Stroke,
+    Fill,
+}
+
+/// Shows the fill and stroke colors of a shape as two overlapping swatches, like vector editors
+/// do, with a button to swap them. Clicking a swatch opens one shared picker for that color;
+/// clicking the other swatch while it is open retargets it.
+pub fn stroke_fill_edit(ui: &mut Ui, stroke: &mut Color32, fill: &mut Color32) -> Response {
+    ui.horizontal(|ui| {
+        let side = ui.spacing().interact_size.y * 1.5;
+        let (rect, button_response) = ui.allocate_exact_size(Vec2::splat(side * 4.0 / 3.0), Sense::click());
+        button_response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
+        let fill_rect = Rect::from_min_size(rect.min, Vec2::splat(side));
+        let stroke_rect = Rect::from_min_size(rect.max - Vec2::splat(side), Vec2::splat(side));
+
+        let target_id = button_response.id.with("target");
+        let popup_id = button_response.id.with("popup");
+        let mut target = temp_get!(ui, target_id, StrokeFillTarget).unwrap_or(StrokeFillTarget::Fill);
+        let top_rect = match target {
+            StrokeFillTarget::Stroke => stroke_rect,
+            StrokeFillTarget::Fill => fill_rect,
+        };
+        if let (true, Some(pos)) = (button_response.clicked(), ui.input().pointer.interact_pos()) {
+            // The swatch being edited is drawn on top, so it wins where they overlap.
+            let clicked = match (top_rect.contains(pos), target) {
+                (true, target) => target,
+                (false, StrokeFillTarget::Stroke) => StrokeFillTarget::Fill,
+                (false, StrokeFillTarget::Fill) => StrokeFillTarget::Stroke,
+            };
+            if clicked != target && compat::is_popup_open(ui.ctx(), popup_id) {
+                // Closed here so that the popup's toggle on click reopens it, for the new target.
+                compat::close_popup(ui.ctx());
+            }
+            target = clicked;
+        }
+        temp_insert!(ui, target_id, target);
+
+        let visuals = ui.style().interact(&button_response);
+        let style = SwatchStyle::default();
+        let painter = ui.painter();
+        let paint_fill = || {
+            paint_swatch(painter, fill_rect, *fill, style);
+            painter.rect_stroke(fill_rect, 0.0, (2.0, visuals.bg_fill));
+        };
+        let paint_stroke = || {
+            // A thick frame, through which the fill shows.
+            let width = side / 4.0;
+            let r = stroke_rect;
+            let strips = [
+                Rect::from_min_max(r.min, pos2(r.right(), r.top() + width)),
+                Rect::from_min_max(pos2(r.left(), r.bottom() - width), r.max),
+                Rect::from_min_max(pos2(r.left(), r.top() + width), pos2(r.left() + width, r.bottom() - width)),
+                Rect::from_min_max(pos2(r.right() - width, r.top() + width), pos2(r.right(), r.bottom() - width)),
+            ];
+            for strip in strips.iter() {
+                paint_swatch(painter, *strip, *stroke, style);
+            }
+            painter.rect_stroke(r, 0.0, (2.0, visuals.bg_fill));
+            painter.rect_stroke(r.shrink(width), 0.0, (2.0, visuals.bg_fill));
+        };
+        match target {
+            StrokeFillTarget::Stroke => {
+                paint_fill();
+                paint_stroke();
+            }
+            StrokeFillTarget::Fill => {
+                paint_stroke();
+                paint_fill();
+            }
+        }
+
+        let edited = match target {
+            StrokeFillTarget::Stroke => &mut *stroke,
+            StrokeFillTarget::Fill => &mut *fill,
+        };
+        let mut col = Srgba::from(edited.into_cint());
+        let mut response = color_edit_button_inner(ui, &mut col, button_response, ButtonTooltip::None)
+            .on_hover_text("Click to edit the stroke or the fill color");
+        *edited = Color32::from_cint(col.to_u8().into());
+
+        if ui.small_button("⇄").on_hover_text("Swap stroke and fill").clicked() {
+            std::mem::swap(stroke, fill);
+            response.mark_changed();
+        }
+        response
+    })
+    .inner
+}
+
+/// Edits a [`Stroke`]: its color with [`color_edit_button`], its width with a [`DragValue`],
+/// and a line preview. A drop-in for egui's `stroke_ui`, without the label.
+pub fn stroke_edit(ui: &mut Ui, stroke: &mut Stroke) -> Response {
+    ui.horizontal(|ui| {
+        let Stroke { width, color } = stroke;
+        let width_response = ui
+            .add(DragValue::new(width).speed(0.1).clamp_range(0.0..=5.0))
+            .on_hover_text("Width");
+        let color_response = color_edit_button(ui, color);
+
+        let (_id, preview_rect) = ui.allocate_space(ui.spacing().interact_size);
+        ui.painter()
+            .line_segment([preview_rect.left_center(), preview_rect.right_center()], (*width, *color));
+
+        width_response.union(color_response)
+    })
+    .inner
+}
+
+/// Like [`color_edit_button`], for Bevy colors. The color is edited in Oklch and written back
+/// in the space it was stored in, e.g. still as `LinearRgba` for linear colors, so that
+/// neither the storage nor the sRGB encoding changes under the caller.
+#[cfg(feature = "bevy")]
+pub fn color_edit_button_bevy(ui: &mut Ui, color: &mut bevy_color::Color) -> Response {
+    use bevy_color::Color as BevyColor;
+
+    let [r, g, b, a] = bevy_color::Srgba::from(*color).to_u8_array();
+    let button_response = color_button(ui, Color32::from_rgba_unmultiplied(r, g, b, a), SwatchStyle::default());
+    let session_id = button_response.id.with("session");
+    let popup_id = button_response.id.with("popup");
+
+    let mut oklch = match temp_get!(ui, session_id, EditSession<BevyColor>) {
+        Some(session) if session.applied == *color => session.oklch,
+        _ => convert::interop::from_bevy(bevy_color::Oklcha::from(*color)),
+    };
+
+    let response = ColorEditButton::new(&mut oklch)
+        .show_with_button(ui, button_response)
+        .response;
+
+    if response.changed() {
+        let edited: BevyColor = convert::interop::to_bevy(oklch).into();
+        *color = match *color {
+            BevyColor::Srgba(_) => BevyColor::Srgba(edited.into()),
+            BevyColor::LinearRgba(_) => BevyColor::LinearRgba(edited.into()),
+            BevyColor::Hsla(_) => BevyColor::Hsla(edited.into()),
+            BevyColor::Hsva(_) => BevyColor::Hsva(edited.into()),
+            BevyColor::Hwba(_) => BevyColor::Hwba(edited.into()),
+            BevyColor::Laba(_) => BevyColor::Laba(edited.into()),
+            BevyColor::Lcha(_) => BevyColor::Lcha(edited.into()),
+            BevyColor::Oklaba(_) => BevyColor::Oklaba(edited.into()),
+            BevyColor::Xyza(_) => BevyColor::Xyza(edited.into()),
+            // Oklcha already, and any spaces added in the future.
+            _ => edited,
+        };
+    }
+    if is_picker_shown(ui, popup_id) {
+        temp_insert!(ui, session_id, EditSession { oklch, applied: *color });
+    } else {
+        temp_remove!(ui, session_id, EditSession<BevyColor>);
+    }
+
+    response
+}
+
+/// [`color_edit_button`] as a [`Widget`], for `ui.add(..)`, `ui.add_enabled(..)` and
+/// `ui.add_sized(..)`.
+pub fn color_edit_widget(color: &mut Color32) -> impl Widget + '_ {
+    move |ui: &mut Ui| color_edit_button(ui, color)
+}
+
+/// [`gradient_edit`] as a [`Widget`].
+pub fn gradient_edit_widget(gradient: &mut Gradient) -> impl Widget + '_ {
+    move |ui: &mut Ui| gradient_edit(ui, gradient)
+}
@@ -0,0 +1,60 @@
+use egui_color_picker_oklab::{convert, OklchA};
+
+/// A spread of colors covering the whole slider ranges, including out-of-gamut and translucent ones.
+fn test_colors() -> Vec<OklchA> {
+    let mut colors = Vec::new();
+    for li in 0..=10 {
+        for ci in 0..=5 {
+            for hi in 0..12 {
+                let alpha = if hi % 3 == 0 { 0.5 } else { 1.0 };
+                colors.push(OklchA::new(
+                    li as f32 / 10.0,
+                    ci as f32 * 0.1,
+                    -std::f32::consts::PI + hi as f32 * std::f32::consts::TAU / 12.0,
+                    alpha,
+                ));
+            }
+        }
+    }
+    colors
+}
+
+#[test]
+fn batch_matches_scalar_bytes() {
+    let colors = test_colors();
+    let batch = convert::oklch_to_srgb8_batch(&colors);
+    assert_eq!(batch.len(), colors.len());
+    for (color, batched) in colors.iter().zip(&batch) {
+        let scalar = convert::oklch_to_srgb8(*color);
+        for (s, b) in scalar.iter().zip(batched) {
+            // The two paths round slightly differently; allow one step.
+            assert!(
+                (*s as i32 - *b as i32).abs() <= 1,
+                "{:?}: scalar {:?}, batched {:?}",
+                color,
+                scalar,
+                batched
+            );
+        }
+    }
+}
+
+#[test]
+fn batch_handles_partial_chunks() {
+    let colors = test_colors();
+    for len in 0..20 {
+        let batch = convert::oklch_to_srgb8_batch(&colors[..len]);
+        let whole = convert::oklch_to_srgb8_batch(&colors);
+        assert_eq!(batch, whole[..len].to_vec());
+    }
+}
+
+#[test]
+fn batch_stays_in_range() {
+    for srgba in convert::oklch_to_srgba_batch(&test_colors()) {
+        for channel in &srgba {
+            assert!((0.0..=1.0).contains(channel), "{:?}", srgba);
+        }
+        assert!(srgba[0] <= srgba[3] && srgba[1] <= srgba[3] && srgba[2] <= srgba[3]);
+    }
+}
@@ -0,0 +1,97 @@
+//! Headless interaction tests: frames are run against a bare `CtxRef` with synthesized pointer
+//! input, and the bound color is checked afterwards.
+
+use egui::{CtxRef, Event, Id, Modifiers, PointerButton, Pos2, RawInput, Rect};
+use egui_color_picker_oklab::{open_color_picker, ColorEditButton, Controls, OklchA};
+
+const PICKER_ID: &str = "interaction_test_picker";
+
+/// Runs one frame with the picker for `color` open, showing `controls`, returning the button's
+/// rect.
+fn frame(ctx: &mut CtxRef, events: Vec<Event>, color: &mut OklchA, controls: Controls) -> Rect {
+    let id = Id::new(PICKER_ID);
+    let mut rect = Rect::NOTHING;
+    ctx.begin_frame(RawInput {
+        events,
+        ..Default::default()
+    });
+    open_color_picker(ctx, id);
+    egui::CentralPanel::default().show(ctx, |ui| {
+        rect = ColorEditButton::new(color).id(id).controls(controls).show(ui).response.rect;
+    });
+    ctx.end_frame();
+    rect
+}
+
+fn button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    }
+}
+
+/// Points on a grid to the lower right of the button, where the popup opens, `step` apart
+/// vertically.
+fn popup_points(button_rect: Rect, step: f32) -> Vec<Pos2> {
+    let mut points = Vec::new();
+    for y in 0..(480.0 / step) as usize {
+        for x in 0..10 {
+            points.push(button_rect.max + egui::vec2(8.0 + x as f32 * 30.0, 8.0 + y as f32 * step));
+        }
+    }
+    points
+}
+
+#[test]
+fn hovering_the_popup_changes_nothing() {
+    let mut ctx = CtxRef::default();
+    let start = OklchA::new(0.6, 0.1, 1.0, 0.8);
+    let mut color = start;
+    let rect = frame(&mut ctx, vec![], &mut color, Controls::ALL);
+    for pos in popup_points(rect, 30.0) {
+        frame(&mut ctx, vec![Event::PointerMoved(pos)], &mut color, Controls::ALL);
+    }
+    assert_eq!(color, start);
+}
+
+#[test]
+fn dragging_the_lightness_slider_right_raises_lightness() {
+    // With only the lightness slider shown, it is the only control a drag can change. Drags move
+    // further than a click may, so they don't press the popup's buttons either.
+    let controls = Controls {
+        lightness: true,
+        ..Controls::NONE
+    };
+    let mut ctx = CtxRef::default();
+    let start = OklchA::new(0.6, 0.1, 1.0, 0.8);
+    let mut color = start;
+    let rect = frame(&mut ctx, vec![], &mut color, controls);
+
+    let mut raised = 0;
+    for pos in popup_points(rect, 8.0) {
+        let to = pos + egui::vec2(20.0, 0.0);
+        frame(&mut ctx, vec![Event::PointerMoved(pos), button(pos, true)], &mut color, controls);
+        let pressed = color;
+        frame(&mut ctx, vec![Event::PointerMoved(to)], &mut color, controls);
+        let dragged = color;
+        frame(&mut ctx, vec![button(to, false)], &mut color, controls);
+
+        assert_eq!(color, dragged, "releasing at {:?} changed the color", to);
+        for edited in &[pressed, dragged] {
+            assert_eq!(
+                (edited.col.c, edited.col.h, edited.alpha),
+                (start.col.c, start.col.h, start.alpha),
+                "dragging from {:?} changed more than lightness",
+                pos
+            );
+            assert!((0.0..=1.0).contains(&edited.col.l), "dragging from {:?} gave {:?}", pos, edited);
+        }
+        assert!(dragged.col.l >= pressed.col.l, "dragging right from {:?} lowered lightness", pos);
+        if dragged.col.l > pressed.col.l {
+            raised += 1;
+        }
+    }
+    assert!(raised > 0, "no drag over the popup hit the lightness slider");
+}
@@ -0,0 +1,57 @@
+use std::f32::consts::PI;
+
+use proptest::prelude::*;
+
+use egui_color_picker_oklab::convert;
+use egui_color_picker_oklab::{GamutPolicy, OklchA, PickerModel};
+
+/// `color` through the picker's chroma reduction, as edited with `clamp_to_gamut`.
+fn reduce_chroma(color: OklchA) -> OklchA {
+    PickerModel::new(OklchA::new(0.5, 0.0, 0.0, 1.0))
+        .with_gamut_policy(GamutPolicy::ReduceChroma)
+        .set_color(color)
+}
+
+/// Difference of two hues in radians, the short way around.
+fn hue_difference(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(2.0 * PI);
+    d.min(2.0 * PI - d)
+}
+
+proptest! {
+    #[test]
+    fn srgb8_round_trips_within_one_step(r: u8, g: u8, b: u8) {
+        let bytes = [r, g, b, 255];
+        let round_tripped = convert::oklch_to_srgb8(convert::srgb8_to_oklch(bytes));
+        for (x, y) in bytes.iter().zip(&round_tripped) {
+            prop_assert!((*x as i32 - *y as i32).abs() <= 1, "{:?} came back as {:?}", bytes, round_tripped);
+        }
+    }
+
+    #[test]
+    fn chroma_reduction_lands_in_gamut(l in 0.0f32..=1.0, c in 0.0f32..0.5, h in -PI..PI) {
+        let reduced = reduce_chroma(OklchA::new(l, c, h, 1.0));
+        prop_assert!(convert::is_in_srgb_gamut(reduced), "{:?} became {:?}", (l, c, h), reduced);
+    }
+
+    #[test]
+    fn chroma_reduction_preserves_hue_and_lightness(l in 0.2f32..0.9, c in 0.0f32..0.5, h in -PI..PI) {
+        let reduced = reduce_chroma(OklchA::new(l, c, h, 1.0));
+        prop_assume!(reduced.col.c > 0.02);
+        prop_assert!(reduced.col.c <= c);
+        prop_assert!((reduced.col.l - l).abs() < 1e-6, "lightness {} became {}", l, reduced.col.l);
+        prop_assert!(hue_difference(h, reduced.col.h) < 1e-2, "hue {} became {}", h, reduced.col.h);
+    }
+
+    #[test]
+    fn css_oklch_round_trips(l in 0.0f32..=1.0, c in 0.0f32..0.4, h in -PI..PI, alpha in 0.0f32..=1.0) {
+        let color = OklchA::new(l, c, h, alpha);
+        let css = convert::to_css_oklch(color);
+        let parsed = convert::from_css_oklch(&css).expect("formatted css should parse");
+        // Within the precision `to_css_oklch` prints at.
+        prop_assert!((parsed.col.l - l).abs() < 1e-3, "{}", css);
+        prop_assert!((parsed.col.c - c).abs() < 1e-4, "{}", css);
+        prop_assert!(hue_difference(parsed.col.h, h) < 2e-3, "{}", css);
+        prop_assert!((parsed.alpha - alpha).abs() < 1e-3, "{}", css);
+    }
+}
@@ -0,0 +1,80 @@
+use egui::{Color32, CtxRef, Event, Modifiers, PointerButton, Pos2, RawInput, Rect};
+use egui_color_picker_oklab::color_edit_button;
+use egui_color_picker_oklab::convert::plain::{oklch_from_srgb8, srgb8_from_oklch};
+
+#[test]
+fn opaque_srgb8_round_trips_exactly() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                let bytes = [r as u8, g as u8, b as u8, 255];
+                assert_eq!(srgb8_from_oklch(oklch_from_srgb8(bytes)), bytes);
+            }
+        }
+    }
+}
+
+#[test]
+fn dark_srgb8_round_trips_exactly() {
+    // Dark colors have the least precision to spare in 8 bits.
+    for v in 0..32u8 {
+        for &bytes in &[[v, v, v, 255], [v, 0, 0, 255], [0, v, 0, 255], [0, 0, v, 255], [v, v / 2, 0, 255]] {
+            assert_eq!(srgb8_from_oklch(oklch_from_srgb8(bytes)), bytes);
+        }
+    }
+}
+
+#[test]
+fn repeated_round_trips_dont_drift() {
+    for &start in &[[12u8, 7, 3, 255], [200, 180, 30, 255], [1, 1, 2, 255], [90, 140, 250, 255], [3, 1, 2, 7]] {
+        let mut bytes = start;
+        for _ in 0..100 {
+            bytes = srgb8_from_oklch(oklch_from_srgb8(bytes));
+        }
+        assert_eq!(bytes, start);
+    }
+}
+
+/// Runs one frame showing `color_edit_button` for `color`, returning the button's rect.
+fn frame(ctx: &mut CtxRef, events: Vec<Event>, color: &mut Color32) -> Rect {
+    let mut rect = Rect::NOTHING;
+    ctx.begin_frame(RawInput {
+        events,
+        ..Default::default()
+    });
+    egui::CentralPanel::default().show(ctx, |ui| rect = color_edit_button(ui, color).rect);
+    ctx.end_frame();
+    rect
+}
+
+fn click(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    }
+}
+
+#[test]
+fn open_picker_leaves_colors_untouched() {
+    // Translucent and dark colors are the ones a quantized round trip per frame would move.
+    for &start in &[
+        Color32::from_rgba_premultiplied(3, 1, 2, 7),
+        Color32::from_rgba_premultiplied(40, 20, 10, 128),
+        Color32::from_rgb(5, 4, 3),
+        Color32::from_gray(128),
+    ] {
+        let mut ctx = CtxRef::default();
+        let mut color = start;
+        let center = frame(&mut ctx, vec![], &mut color).center();
+        frame(&mut ctx, vec![Event::PointerMoved(center), click(center, true)], &mut color);
+        frame(&mut ctx, vec![click(center, false)], &mut color);
+        assert!(ctx.memory().any_popup_open(), "clicking the button should open the picker");
+
+        for _ in 0..60 {
+            frame(&mut ctx, vec![], &mut color);
+            assert_eq!(color, start);
+        }
+    }
+}